@@ -0,0 +1,34 @@
+// spins the stanford bunny in a window, move around with WASD, Q & E & look around with the arrow keys
+use rust_graphics::{algebra::vector3::*, topology::{color::*, mesh::*, model::*}, video::{render::*, screen::*, view::*}};
+
+fn main()
+{
+    let screen = Screen::create(800, 600);
+    let view = View::create(&Vector3::create(0.0, 0.0, -3.0), &Vector3::create(0.0, 0.0, 0.0));
+
+    let mut render = Render::create(screen, view);
+
+//  let mut mesh: Mesh = Mesh::sphere(40, 20, 1.0);
+
+    let model_data = Model::load_obj("assets/bunny.obj", 1.0).unwrap_or_else(|error|
+    {
+        eprintln!("Error : failed to load 'assets/bunny.obj' ({})", error);
+
+        std::process::exit(1);
+    });
+    let mut model: Mesh = Mesh::converted(&model_data);
+
+    loop
+    {
+        render.target.input(&mut render.view);
+        render.target.tick();
+        
+        render.clear(Color::unpacked(0x181818FF));
+
+        model.rotation.y += 30.0 * render.target.delta_time;
+
+        render.process(&mut model);
+        
+        render.update();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,135 @@
+use super::{*, vector3::*};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Matrix3x3
+{
+    // matrix data are in row-major order
+    data: [[f32; 3]; 3],
+}
+
+impl Matrix3x3
+{
+    pub fn identity() -> Self
+    {
+        let mut data = [[0.0; 3]; 3];
+
+        for (i, row) in data.iter_mut().enumerate()
+        {
+            row[i] = 1.0;
+        }
+
+        Self { data }
+    }
+
+    pub fn create(data: [[f32; 3]; 3]) -> Self
+    {
+        Self { data }
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> f32
+    {
+        self.data[row][column]
+    }
+
+    pub fn transpose(&self) -> Self
+    {
+        let mut result: Matrix3x3 = *self;
+
+        for i in 0..3
+        {
+            for j in 0..3
+            {
+                result.data[i][j] = self.data[j][i];
+            }
+        }
+
+        result
+    }
+
+    pub fn determinant(&self) -> f32
+    {
+        let m = &self.data;
+
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1]) -
+        m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0]) +
+        m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    // the adjugate divided by the determinant, none for singular matrices
+    pub fn inverse(&self) -> Option<Self>
+    {
+        let determinant: f32 = self.determinant();
+
+        // relative to the size of the entries, so uniformly small matrices (a model in millimeters) still have an inverse
+        let largest: f32 = self.data.iter().flatten().fold(0.0, |largest: f32, value| largest.max(value.abs()));
+
+        if !determinant.is_finite() || determinant.abs() <= f32::EPSILON * largest * largest * largest { return None; }
+
+        let m = &self.data;
+
+        let adjugate: [[f32; 3]; 3] =
+        [
+            [m[1][1] * m[2][2] - m[1][2] * m[2][1], m[0][2] * m[2][1] - m[0][1] * m[2][2], m[0][1] * m[1][2] - m[0][2] * m[1][1]],
+            [m[1][2] * m[2][0] - m[1][0] * m[2][2], m[0][0] * m[2][2] - m[0][2] * m[2][0], m[0][2] * m[1][0] - m[0][0] * m[1][2]],
+            [m[1][0] * m[2][1] - m[1][1] * m[2][0], m[0][1] * m[2][0] - m[0][0] * m[2][1], m[0][0] * m[1][1] - m[0][1] * m[1][0]],
+        ];
+
+        Some(Self { data: adjugate.map(|row| row.map(|value| value / determinant)) })
+    }
+}
+
+impl std::ops::Mul<Matrix3x3> for Matrix3x3
+{
+    type Output = Matrix3x3;
+
+    fn mul(self, scalar: Matrix3x3) -> Matrix3x3
+    {
+        let mut result: Matrix3x3 = Matrix3x3::identity();
+
+        for i in 0..3
+        {
+            for j in 0..3
+            {
+                result.data[i][j] =
+
+                self.data[i][0] * scalar.data[0][j] +
+                self.data[i][1] * scalar.data[1][j] +
+                self.data[i][2] * scalar.data[2][j];
+            }
+        }
+
+        result
+    }
+}
+
+impl std::ops::Mul<Vector3> for Matrix3x3
+{
+    type Output = Vector3;
+
+    fn mul(self, scalar: Vector3) -> Vector3
+    {
+        Vector3
+        {
+            x: self.data[0][0] * scalar.x + self.data[0][1] * scalar.y + self.data[0][2] * scalar.z,
+            y: self.data[1][0] * scalar.x + self.data[1][1] * scalar.y + self.data[1][2] * scalar.z,
+            z: self.data[2][0] * scalar.x + self.data[2][1] * scalar.y + self.data[2][2] * scalar.z,
+        }
+    }
+}
+
+impl std::ops::MulAssign<Matrix3x3> for Matrix3x3
+{
+    fn mul_assign(&mut self, scalar: Matrix3x3)
+    {
+        *self = *self * scalar;
+    }
+}
+
+// elements are compared within an epsilon
+impl PartialEq for Matrix3x3
+{
+    fn eq(&self, other: &Matrix3x3) -> bool
+    {
+        self.data.iter().flatten().zip(other.data.iter().flatten()).all(|(a, b)| approximately(*a, *b))
+    }
+}
@@ -1,6 +1,10 @@
-use super::vector3::*;
+use super::{*, matrix3x3::*, vector3::*, vector4::*};
 
-#[derive(Clone, Copy)]
+// the order in which the euler angles are applied, XYZ rotates around x first & around z last
+#[derive(Clone, Copy, PartialEq)]
+pub enum Order { XYZ, XZY, YXZ, YZX, ZXY, ZYX }
+
+#[derive(Clone, Copy, Debug)]
 pub struct Matrix4x4
 {
     // matrix data are in row-major order
@@ -13,14 +17,19 @@ impl Matrix4x4
     {
         let mut data = [[0.0; 4]; 4];
 
-        for i in 0..4
+        for (i, row) in data.iter_mut().enumerate()
         {
-            data[i][i] = 1.0;
+            row[i] = 1.0;
         }
 
         Self { data }
     }
 
+    pub fn create(data: [[f32; 4]; 4]) -> Self
+    {
+        Self { data }
+    }
+
     pub fn translate(x: f32, y:f32, z: f32) -> Self
     {
         let mut result: Matrix4x4 = Matrix4x4::identity();
@@ -55,6 +64,46 @@ impl Matrix4x4
         result
     }
 
+    pub fn rotate_x(angle: f32) -> Self
+    {
+        let mut result: Matrix4x4 = Matrix4x4::identity();
+
+        result.data[1][1] = f32::cos(f32::to_radians(angle));
+        result.data[1][2] = -f32::sin(f32::to_radians(angle));
+        result.data[2][1] = f32::sin(f32::to_radians(angle));
+        result.data[2][2] = f32::cos(f32::to_radians(angle));
+
+        result
+    }
+
+    pub fn rotate_z(angle: f32) -> Self
+    {
+        let mut result: Matrix4x4 = Matrix4x4::identity();
+
+        result.data[0][0] = f32::cos(f32::to_radians(angle));
+        result.data[0][1] = -f32::sin(f32::to_radians(angle));
+        result.data[1][0] = f32::sin(f32::to_radians(angle));
+        result.data[1][1] = f32::cos(f32::to_radians(angle));
+
+        result
+    }
+
+    // rotation by euler angles (in degrees), the first axis of the order is applied first
+    pub fn rotate(angles: &Vector3, order: Order) -> Self
+    {
+        let (x, y, z) = (Matrix4x4::rotate_x(angles.x), Matrix4x4::rotate_y(angles.y), Matrix4x4::rotate_z(angles.z));
+
+        match order
+        {
+            Order::XYZ => z * y * x,
+            Order::XZY => y * z * x,
+            Order::YXZ => z * x * y,
+            Order::YZX => x * z * y,
+            Order::ZXY => y * x * z,
+            Order::ZYX => x * y * z,
+        }
+    }
+
     pub fn view(eye: &Vector3, at: &Vector3, up: &Vector3) -> Self
     {
         let mut view: Matrix4x4 = Matrix4x4::identity();
@@ -99,10 +148,145 @@ impl Matrix4x4
         projection
     }
 
+    // projection matrix - perspective, with the frustum given by its bounds on the near plane (they can be off-center)
+    pub fn frustum(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self
+    {
+        let mut projection: Matrix4x4 = Matrix4x4::identity();
+
+        projection.data[0][0] = 2.0 * near / (right - left);
+        projection.data[0][2] = -(right + left) / (right - left);
+        projection.data[1][1] = 2.0 * near / (top - bottom);
+        projection.data[1][2] = -(top + bottom) / (top - bottom);
+        projection.data[2][2] = far / (far - near);
+        projection.data[2][3] = (-far * near) / (far - near);
+        projection.data[3][2] = 1.0;
+        projection.data[3][3] = 0.0;
+
+        projection
+    }
+
+    // projection matrix - orthographic, the view volume is a box
+    pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self
+    {
+        let mut projection: Matrix4x4 = Matrix4x4::identity();
+
+        projection.data[0][0] = 2.0 / (right - left);
+        projection.data[0][3] = -(right + left) / (right - left);
+        projection.data[1][1] = 2.0 / (top - bottom);
+        projection.data[1][3] = -(top + bottom) / (top - bottom);
+        projection.data[2][2] = 1.0 / (far - near);
+        projection.data[2][3] = -near / (far - near);
+
+        projection
+    }
+
     pub fn get(&self, row: usize, column: usize) -> f32
     {
         self.data[row][column]
     }
+
+    // the upper left part, which holds the rotation & scale
+    pub fn upper(&self) -> Matrix3x3
+    {
+        Matrix3x3::create([0, 1, 2].map(|i| [self.data[i][0], self.data[i][1], self.data[i][2]]))
+    }
+
+    pub fn transpose(&self) -> Self
+    {
+        let mut result: Matrix4x4 = *self;
+
+        for i in 0..4
+        {
+            for j in 0..4
+            {
+                result.data[i][j] = self.data[j][i];
+            }
+        }
+
+        result
+    }
+
+    // gaussian elimination, the determinant is the product of the pivots
+    pub fn determinant(&self) -> f32
+    {
+        let mut m: [[f32; 4]; 4] = self.data;
+        let mut determinant: f32 = 1.0;
+
+        for column in 0..4
+        {
+            let pivot: usize = (column..4).max_by(|a, b| m[*a][column].abs().total_cmp(&m[*b][column].abs())).unwrap();
+
+            if m[pivot][column] == 0.0 { return 0.0; }
+
+            // swapping rows flips the sign
+            if pivot != column
+            {
+                m.swap(pivot, column);
+                determinant = -determinant;
+            }
+
+            determinant *= m[column][column];
+
+            let pivot_row: [f32; 4] = m[column];
+
+            for row in m.iter_mut().skip(column + 1)
+            {
+                let factor: f32 = row[column] / pivot_row[column];
+
+                for (value, pivot_value) in row.iter_mut().zip(pivot_row).skip(column)
+                {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+
+        determinant
+    }
+
+    // gauss-jordan elimination with partial pivoting, none for singular matrices
+    pub fn inverse(&self) -> Option<Self>
+    {
+        let mut m: [[f32; 4]; 4] = self.data;
+        let mut inverse: [[f32; 4]; 4] = Matrix4x4::identity().data;
+
+        // the pivots are compared to the size of the entries, so uniformly small matrices still have an inverse
+        let largest: f32 = self.data.iter().flatten().fold(0.0, |largest: f32, value| largest.max(value.abs()));
+
+        if !largest.is_finite() { return None; }
+
+        for column in 0..4
+        {
+            let pivot: usize = (column..4).max_by(|a, b| m[*a][column].abs().total_cmp(&m[*b][column].abs())).unwrap();
+
+            if m[pivot][column].abs() <= f32::EPSILON * largest { return None; }
+
+            m.swap(pivot, column);
+            inverse.swap(pivot, column);
+
+            let scale: f32 = 1.0 / m[column][column];
+
+            for j in 0..4
+            {
+                m[column][j] *= scale;
+                inverse[column][j] *= scale;
+            }
+
+            for row in 0..4
+            {
+                if row == column { continue; }
+
+                let factor: f32 = m[row][column];
+
+                for j in 0..4
+                {
+                    m[row][j] -= factor * m[column][j];
+                    inverse[row][j] -= factor * inverse[column][j];
+                }
+            }
+        }
+
+        Some(Self { data: inverse })
+    }
 }
 
 impl std::ops::Mul<Matrix4x4> for Matrix4x4
@@ -128,4 +312,37 @@ impl std::ops::Mul<Matrix4x4> for Matrix4x4
 
         Matrix4x4 { data: result.data }
     }
-}
\ No newline at end of file
+}
+
+impl std::ops::Mul<Vector4> for Matrix4x4
+{
+    type Output = Vector4;
+
+    fn mul(self, scalar: Vector4) -> Vector4
+    {
+        Vector4
+        {
+            x: self.data[0][0] * scalar.x + self.data[0][1] * scalar.y + self.data[0][2] * scalar.z + self.data[0][3] * scalar.w,
+            y: self.data[1][0] * scalar.x + self.data[1][1] * scalar.y + self.data[1][2] * scalar.z + self.data[1][3] * scalar.w,
+            z: self.data[2][0] * scalar.x + self.data[2][1] * scalar.y + self.data[2][2] * scalar.z + self.data[2][3] * scalar.w,
+            w: self.data[3][0] * scalar.x + self.data[3][1] * scalar.y + self.data[3][2] * scalar.z + self.data[3][3] * scalar.w,
+        }
+    }
+}
+
+impl std::ops::MulAssign<Matrix4x4> for Matrix4x4
+{
+    fn mul_assign(&mut self, scalar: Matrix4x4)
+    {
+        *self = *self * scalar;
+    }
+}
+
+// elements are compared within an epsilon
+impl PartialEq for Matrix4x4
+{
+    fn eq(&self, other: &Matrix4x4) -> bool
+    {
+        self.data.iter().flatten().zip(other.data.iter().flatten()).all(|(a, b)| approximately(*a, *b))
+    }
+}
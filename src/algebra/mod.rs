@@ -1,2 +1,20 @@
+pub mod vector2;
 pub mod vector3;
-pub mod matrix4x4;
\ No newline at end of file
+pub mod vector4;
+pub mod matrix3x3;
+pub mod matrix4x4;
+pub mod quaternion;
+
+// tolerance of the approximate comparisons, relative to the magnitude of the values past 1
+pub const EPSILON: f32 = 1e-5;
+
+pub fn approximately(a: f32, b: f32) -> bool
+{
+    (a - b).abs() <= EPSILON * f32::max(1.0, f32::max(a.abs(), b.abs()))
+}
+
+// linear interpolation, t = 0 gives a & t = 1 gives b
+pub fn lerp(a: f32, b: f32, t: f32) -> f32
+{
+    a + (b - a) * t
+}
@@ -0,0 +1,152 @@
+use super::{matrix4x4::*, vector3::*};
+
+// unit quaternions represent orientations without the gimbal lock of euler angles
+#[derive(Clone, Copy, Debug)]
+pub struct Quaternion
+{
+    pub w: f32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Quaternion
+{
+    pub fn create(w: f32, x: f32, y: f32, z: f32) -> Self
+    {
+        Self { w, x, y, z }
+    }
+
+    pub fn identity() -> Self
+    {
+        Self { w: 1.0, x: 0.0, y: 0.0, z: 0.0 }
+    }
+
+    // rotation of the given angle (in degrees) around the axis
+    pub fn axis_angle(axis: &Vector3, angle: f32) -> Self
+    {
+        let axis: Vector3 = axis.normalized();
+        let half: f32 = f32::to_radians(angle) / 2.0;
+
+        Self { w: f32::cos(half), x: axis.x * f32::sin(half), y: axis.y * f32::sin(half), z: axis.z * f32::sin(half) }
+    }
+
+    // the same rotation as Matrix4x4::rotate with these euler angles
+    pub fn euler(angles: &Vector3, order: Order) -> Self
+    {
+        let x: Quaternion = Quaternion::axis_angle(&Vector3::create(1.0, 0.0, 0.0), angles.x);
+        let y: Quaternion = Quaternion::axis_angle(&Vector3::create(0.0, 1.0, 0.0), angles.y);
+        let z: Quaternion = Quaternion::axis_angle(&Vector3::create(0.0, 0.0, 1.0), angles.z);
+
+        match order
+        {
+            Order::XYZ => z * y * x,
+            Order::XZY => y * z * x,
+            Order::YXZ => z * x * y,
+            Order::YZX => x * z * y,
+            Order::ZXY => y * x * z,
+            Order::ZYX => x * y * z,
+        }
+    }
+
+    // the axis & angle (in degrees) of the rotation, any axis works for the identity
+    pub fn to_axis_angle(self) -> (Vector3, f32)
+    {
+        let q: Quaternion = self.normalized();
+
+        let sine: f32 = f32::sqrt(1.0 - q.w * q.w);
+
+        let axis: Vector3 = if sine > 1e-6 { Vector3::create(q.x, q.y, q.z) / sine } else { Vector3::create(1.0, 0.0, 0.0) };
+
+        (axis, f32::to_degrees(2.0 * q.w.clamp(-1.0, 1.0).acos()))
+    }
+
+    pub fn length(&self) -> f32
+    {
+        (self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    pub fn normalized(&self) -> Self
+    {
+        let length: f32 = self.length();
+
+        Self { w: self.w / length, x: self.x / length, y: self.y / length, z: self.z / length }
+    }
+
+    // the inverse rotation (for unit quaternions)
+    pub fn conjugate(&self) -> Self
+    {
+        Self { w: self.w, x: -self.x, y: -self.y, z: -self.z }
+    }
+
+    pub fn dot(q1: &Quaternion, q2: &Quaternion) -> f32
+    {
+        q1.w * q2.w + q1.x * q2.x + q1.y * q2.y + q1.z * q2.z
+    }
+
+    // spherical linear interpolation, always along the shortest arc
+    pub fn slerp(q1: &Quaternion, q2: &Quaternion, t: f32) -> Self
+    {
+        let mut cosine: f32 = Quaternion::dot(q1, q2);
+
+        // q & -q are the same rotation, pick the closest one
+        let q2: Quaternion = if cosine < 0.0 { cosine = -cosine; Quaternion::create(-q2.w, -q2.x, -q2.y, -q2.z) } else { *q2 };
+
+        // nearly identical rotations, a linear interpolation is precise enough & avoids dividing by zero
+        let (a, b) = if cosine > 0.9995
+        {
+            (1.0 - t, t)
+        }
+        else
+        {
+            let angle: f32 = cosine.acos();
+
+            (f32::sin((1.0 - t) * angle) / f32::sin(angle), f32::sin(t * angle) / f32::sin(angle))
+        };
+
+        Quaternion::create
+        (
+            q1.w * a + q2.w * b,
+            q1.x * a + q2.x * b,
+            q1.y * a + q2.y * b,
+            q1.z * a + q2.z * b,
+        ).normalized()
+    }
+
+    pub fn rotate(&self, vector: &Vector3) -> Vector3
+    {
+        let p: Quaternion = *self * Quaternion::create(0.0, vector.x, vector.y, vector.z) * self.conjugate();
+
+        Vector3::create(p.x, p.y, p.z)
+    }
+
+    pub fn matrix(&self) -> Matrix4x4
+    {
+        let Quaternion { w, x, y, z } = self.normalized();
+
+        Matrix4x4::create
+        ([
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y), 0.0],
+            [2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x), 0.0],
+            [2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y), 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+}
+
+// combines two rotations, the one on the right is applied first
+impl std::ops::Mul<Quaternion> for Quaternion
+{
+    type Output = Quaternion;
+
+    fn mul(self, scalar: Quaternion) -> Quaternion
+    {
+        Quaternion
+        {
+            w: self.w * scalar.w - self.x * scalar.x - self.y * scalar.y - self.z * scalar.z,
+            x: self.w * scalar.x + self.x * scalar.w + self.y * scalar.z - self.z * scalar.y,
+            y: self.w * scalar.y - self.x * scalar.z + self.y * scalar.w + self.z * scalar.x,
+            z: self.w * scalar.z + self.x * scalar.y - self.y * scalar.x + self.z * scalar.w,
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Vector2
+{
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vector2
+{
+    pub fn create(x: f32, y: f32) -> Self
+    {
+        Self { x, y }
+    }
+
+    pub fn zero() -> Self
+    {
+        Self { x: 0.0, y: 0.0 }
+    }
+
+    pub fn length(&self) -> f32
+    {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
+
+    pub fn opposite(&self) -> Self
+    {
+        Self { x: -self.x, y: -self.y }
+    }
+
+    pub fn normalized(&self) -> Self
+    {
+        let length: f32 = self.length();
+
+        Self { x: self.x / length, y: self.y / length }
+    }
+
+    pub fn dot(v1: &Vector2, v2: &Vector2) -> f32
+    {
+        v1.x * v2.x + v1.y * v2.y
+    }
+
+    // linear interpolation, t = 0 gives a & t = 1 gives b
+    pub fn lerp(a: &Vector2, b: &Vector2, t: f32) -> Self
+    {
+        Self { x: a.x + (b.x - a.x) * t, y: a.y + (b.y - a.y) * t }
+    }
+
+    // clamps every component to [min, max]
+    pub fn clamp(&self, min: f32, max: f32) -> Self
+    {
+        Self { x: self.x.clamp(min, max), y: self.y.clamp(min, max) }
+    }
+}
+
+impl std::ops::Add<Vector2> for Vector2
+{
+    type Output = Vector2;
+
+    fn add(self, scalar: Vector2) -> Vector2
+    {
+        Vector2
+        {
+            x: self.x + scalar.x,
+            y: self.y + scalar.y,
+        }
+    }
+}
+
+impl std::ops::Sub<Vector2> for Vector2
+{
+    type Output = Vector2;
+
+    fn sub(self, scalar: Vector2) -> Vector2
+    {
+        Vector2
+        {
+            x: self.x - scalar.x,
+            y: self.y - scalar.y,
+        }
+    }
+}
+
+impl std::ops::Mul<f32> for Vector2
+{
+    type Output = Vector2;
+
+    fn mul(self, scalar: f32) -> Vector2
+    {
+        Vector2
+        {
+            x: self.x * scalar,
+            y: self.y * scalar,
+        }
+    }
+}
+
+impl std::ops::Mul<Vector2> for Vector2
+{
+    type Output = Vector2;
+
+    fn mul(self, scalar: Vector2) -> Vector2
+    {
+        Vector2
+        {
+            x: self.x * scalar.x,
+            y: self.y * scalar.y,
+        }
+    }
+}
+
+impl std::ops::Div<f32> for Vector2
+{
+    type Output = Vector2;
+
+    fn div(self, scalar: f32) -> Vector2
+    {
+        Vector2
+        {
+            x: self.x / scalar,
+            y: self.y / scalar,
+        }
+    }
+}
+
+impl std::ops::Neg for Vector2
+{
+    type Output = Vector2;
+
+    fn neg(self) -> Vector2
+    {
+        Vector2
+        {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl std::ops::AddAssign<Vector2> for Vector2
+{
+    fn add_assign(&mut self, scalar: Vector2)
+    {
+        self.x += scalar.x;
+        self.y += scalar.y;
+    }
+}
+
+impl std::ops::SubAssign<Vector2> for Vector2
+{
+    fn sub_assign(&mut self, scalar: Vector2)
+    {
+        self.x -= scalar.x;
+        self.y -= scalar.y;
+    }
+}
+
+impl std::ops::MulAssign<f32> for Vector2
+{
+    fn mul_assign(&mut self, scalar: f32)
+    {
+        self.x *= scalar;
+        self.y *= scalar;
+    }
+}
+
+impl std::ops::DivAssign<f32> for Vector2
+{
+    fn div_assign(&mut self, scalar: f32)
+    {
+        self.x /= scalar;
+        self.y /= scalar;
+    }
+}
+
+// components are compared within an epsilon, so results of float arithmetic can be checked for equality
+impl PartialEq for Vector2
+{
+    fn eq(&self, other: &Vector2) -> bool
+    {
+        approximately(self.x, other.x) && approximately(self.y, other.y)
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Clone, Copy)]
+use super::*;
+
+#[derive(Clone, Copy, Debug)]
 pub struct Vector3
 {
     pub x: f32,
@@ -45,12 +47,24 @@ impl Vector3
 
     pub fn reflect(incident: &Vector3, normal: &Vector3) -> Self
     {
-        let twodot: f32 = 2.0 * Vector3::dot(&incident, &normal);
+        let twodot: f32 = 2.0 * Vector3::dot(incident, normal);
         
         let result: Vector3 = (*incident - *normal * twodot).normalized();
 
         result
     }
+
+    // linear interpolation, t = 0 gives a & t = 1 gives b
+    pub fn lerp(a: &Vector3, b: &Vector3, t: f32) -> Self
+    {
+        Self { x: a.x + (b.x - a.x) * t, y: a.y + (b.y - a.y) * t, z: a.z + (b.z - a.z) * t }
+    }
+
+    // clamps every component to [min, max]
+    pub fn clamp(&self, min: f32, max: f32) -> Self
+    {
+        Self { x: self.x.clamp(min, max), y: self.y.clamp(min, max), z: self.z.clamp(min, max) }
+    }
 }
 
 impl std::ops::Add for Vector3
@@ -126,4 +140,68 @@ impl std::ops::Div<f32> for Vector3
             z: self.z / scalar,
         }
     }
-}
\ No newline at end of file
+}
+
+impl std::ops::Neg for Vector3
+{
+    type Output = Vector3;
+
+    fn neg(self) -> Vector3
+    {
+        Vector3
+        {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl std::ops::AddAssign<Vector3> for Vector3
+{
+    fn add_assign(&mut self, scalar: Vector3)
+    {
+        self.x += scalar.x;
+        self.y += scalar.y;
+        self.z += scalar.z;
+    }
+}
+
+impl std::ops::SubAssign<Vector3> for Vector3
+{
+    fn sub_assign(&mut self, scalar: Vector3)
+    {
+        self.x -= scalar.x;
+        self.y -= scalar.y;
+        self.z -= scalar.z;
+    }
+}
+
+impl std::ops::MulAssign<f32> for Vector3
+{
+    fn mul_assign(&mut self, scalar: f32)
+    {
+        self.x *= scalar;
+        self.y *= scalar;
+        self.z *= scalar;
+    }
+}
+
+impl std::ops::DivAssign<f32> for Vector3
+{
+    fn div_assign(&mut self, scalar: f32)
+    {
+        self.x /= scalar;
+        self.y /= scalar;
+        self.z /= scalar;
+    }
+}
+
+// components are compared within an epsilon, so results of float arithmetic can be checked for equality
+impl PartialEq for Vector3
+{
+    fn eq(&self, other: &Vector3) -> bool
+    {
+        approximately(self.x, other.x) && approximately(self.y, other.y) && approximately(self.z, other.z)
+    }
+}
@@ -0,0 +1,223 @@
+use super::{*, vector3::*};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Vector4
+{
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Vector4
+{
+    pub fn create(x: f32, y: f32, z: f32, w: f32) -> Self
+    {
+        Self { x, y, z, w }
+    }
+
+    pub fn zero() -> Self
+    {
+        Self { x: 0.0, y: 0.0, z: 0.0, w: 0.0 }
+    }
+
+    // a point (w = 1) or a direction (w = 0) in homogeneous coordinates
+    pub fn extended(v: &Vector3, w: f32) -> Self
+    {
+        Self { x: v.x, y: v.y, z: v.z, w }
+    }
+
+    pub fn xyz(&self) -> Vector3
+    {
+        Vector3::create(self.x, self.y, self.z)
+    }
+
+    // back from homogeneous coordinates
+    pub fn projected(&self) -> Vector3
+    {
+        Vector3::create(self.x / self.w, self.y / self.w, self.z / self.w)
+    }
+
+    pub fn length(&self) -> f32
+    {
+        (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt()
+    }
+
+    pub fn opposite(&self) -> Self
+    {
+        Self { x: -self.x, y: -self.y, z: -self.z, w: -self.w }
+    }
+
+    pub fn normalized(&self) -> Self
+    {
+        let length: f32 = self.length();
+
+        Self { x: self.x / length, y: self.y / length, z: self.z / length, w: self.w / length }
+    }
+
+    pub fn dot(v1: &Vector4, v2: &Vector4) -> f32
+    {
+        v1.x * v2.x + v1.y * v2.y + v1.z * v2.z + v1.w * v2.w
+    }
+
+    // linear interpolation, t = 0 gives a & t = 1 gives b
+    pub fn lerp(a: &Vector4, b: &Vector4, t: f32) -> Self
+    {
+        Self { x: a.x + (b.x - a.x) * t, y: a.y + (b.y - a.y) * t, z: a.z + (b.z - a.z) * t, w: a.w + (b.w - a.w) * t }
+    }
+
+    // clamps every component to [min, max]
+    pub fn clamp(&self, min: f32, max: f32) -> Self
+    {
+        Self { x: self.x.clamp(min, max), y: self.y.clamp(min, max), z: self.z.clamp(min, max), w: self.w.clamp(min, max) }
+    }
+}
+
+impl std::ops::Add<Vector4> for Vector4
+{
+    type Output = Vector4;
+
+    fn add(self, scalar: Vector4) -> Vector4
+    {
+        Vector4
+        {
+            x: self.x + scalar.x,
+            y: self.y + scalar.y,
+            z: self.z + scalar.z,
+            w: self.w + scalar.w,
+        }
+    }
+}
+
+impl std::ops::Sub<Vector4> for Vector4
+{
+    type Output = Vector4;
+
+    fn sub(self, scalar: Vector4) -> Vector4
+    {
+        Vector4
+        {
+            x: self.x - scalar.x,
+            y: self.y - scalar.y,
+            z: self.z - scalar.z,
+            w: self.w - scalar.w,
+        }
+    }
+}
+
+impl std::ops::Mul<f32> for Vector4
+{
+    type Output = Vector4;
+
+    fn mul(self, scalar: f32) -> Vector4
+    {
+        Vector4
+        {
+            x: self.x * scalar,
+            y: self.y * scalar,
+            z: self.z * scalar,
+            w: self.w * scalar,
+        }
+    }
+}
+
+impl std::ops::Mul<Vector4> for Vector4
+{
+    type Output = Vector4;
+
+    fn mul(self, scalar: Vector4) -> Vector4
+    {
+        Vector4
+        {
+            x: self.x * scalar.x,
+            y: self.y * scalar.y,
+            z: self.z * scalar.z,
+            w: self.w * scalar.w,
+        }
+    }
+}
+
+impl std::ops::Div<f32> for Vector4
+{
+    type Output = Vector4;
+
+    fn div(self, scalar: f32) -> Vector4
+    {
+        Vector4
+        {
+            x: self.x / scalar,
+            y: self.y / scalar,
+            z: self.z / scalar,
+            w: self.w / scalar,
+        }
+    }
+}
+
+impl std::ops::Neg for Vector4
+{
+    type Output = Vector4;
+
+    fn neg(self) -> Vector4
+    {
+        Vector4
+        {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: -self.w,
+        }
+    }
+}
+
+impl std::ops::AddAssign<Vector4> for Vector4
+{
+    fn add_assign(&mut self, scalar: Vector4)
+    {
+        self.x += scalar.x;
+        self.y += scalar.y;
+        self.z += scalar.z;
+        self.w += scalar.w;
+    }
+}
+
+impl std::ops::SubAssign<Vector4> for Vector4
+{
+    fn sub_assign(&mut self, scalar: Vector4)
+    {
+        self.x -= scalar.x;
+        self.y -= scalar.y;
+        self.z -= scalar.z;
+        self.w -= scalar.w;
+    }
+}
+
+impl std::ops::MulAssign<f32> for Vector4
+{
+    fn mul_assign(&mut self, scalar: f32)
+    {
+        self.x *= scalar;
+        self.y *= scalar;
+        self.z *= scalar;
+        self.w *= scalar;
+    }
+}
+
+impl std::ops::DivAssign<f32> for Vector4
+{
+    fn div_assign(&mut self, scalar: f32)
+    {
+        self.x /= scalar;
+        self.y /= scalar;
+        self.z /= scalar;
+        self.w /= scalar;
+    }
+}
+
+// components are compared within an epsilon, so results of float arithmetic can be checked for equality
+impl PartialEq for Vector4
+{
+    fn eq(&self, other: &Vector4) -> bool
+    {
+        approximately(self.x, other.x) && approximately(self.y, other.y) && approximately(self.z, other.z) && approximately(self.w, other.w)
+    }
+}
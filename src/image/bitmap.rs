@@ -0,0 +1,100 @@
+use super::{ppm, tga, bmp, png};
+
+use std::{fs, io, path::Path};
+
+// a plain 2D image, used to move pixels in and out of files
+pub struct Bitmap
+{
+    pub width: i32,
+    pub height: i32,
+
+    // packed RGBA pixels (0xRRGGBBAA) in row-major order, starting at the top left corner
+    pub pixels: Vec<u32>,
+}
+
+impl Bitmap
+{
+    pub fn create(width: i32, height: i32, pixels: Vec<u32>) -> Self
+    {
+        assert_eq!(pixels.len(), (width * height) as usize, "Error : pixel count does not match the image size");
+
+        Self { width, height, pixels }
+    }
+
+    // visualizes a depth buffer as a grayscale image, closer surfaces are brighter
+    // the values that were written are stretched over the full range, the cleared background stays black
+    pub fn grayscale(width: i32, height: i32, depth_buffer: &[f32]) -> Self
+    {
+        let written = depth_buffer.iter().copied().filter(|z| *z < 1.0);
+
+        let near: f32 = written.clone().fold(1.0, f32::min);
+        let far: f32 = written.fold(near, f32::max);
+
+        let range: f32 = if far > near { far - near } else { 1.0 };
+
+        let pixels: Vec<u32> = depth_buffer.iter().map(|z|
+        {
+            if *z >= 1.0 { return 0x000000FF; }
+
+            let level: u32 = ((1.0 - (z - near) / range) * 255.0) as u8 as u32;
+
+            (level << 24) | (level << 16) | (level << 8) | 0xFF
+        })
+        .collect();
+
+        Self { width, height, pixels }
+    }
+
+    // 8-bit RGB triplets without the alpha channel
+    pub fn rgb(&self) -> Vec<u8>
+    {
+        self.pixels.iter().flat_map(|p| [(p >> 24) as u8, (p >> 16) as u8, (p >> 8) as u8]).collect()
+    }
+
+    // 8-bit RGBA quadruplets
+    pub fn rgba(&self) -> Vec<u8>
+    {
+        self.pixels.iter().flat_map(|p| p.to_be_bytes()).collect()
+    }
+
+    // reads an image from disk, the format is picked from the file extension (.ppm, .tga, .bmp or .png)
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self>
+    {
+        let path = path.as_ref();
+
+        let data: Vec<u8> = fs::read(path)?;
+
+        match extension(path).as_str()
+        {
+            "ppm" => ppm::decode(&data),
+            "tga" => tga::decode(&data),
+            "bmp" => bmp::decode(&data),
+            "png" => png::decode(&data),
+
+            other => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("unsupported image format '{}'", other))),
+        }
+    }
+
+    // writes the image to disk, the format is picked from the file extension (.ppm, .tga or .png)
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()>
+    {
+        let path = path.as_ref();
+
+        let data: Vec<u8> = match extension(path).as_str()
+        {
+            "ppm" => ppm::encode(self),
+            "tga" => tga::encode(self),
+            "png" => png::encode(self),
+
+            other => return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("unsupported image format '{}'", other))),
+        };
+
+        fs::write(path, data)
+    }
+}
+
+
+fn extension(path: &Path) -> String
+{
+    path.extension().and_then(|e| e.to_str()).unwrap_or("").to_ascii_lowercase()
+}
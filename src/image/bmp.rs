@@ -0,0 +1,93 @@
+use super::bitmap::*;
+
+use std::io;
+
+fn invalid(message: &str) -> io::Error
+{
+    io::Error::new(io::ErrorKind::InvalidData, format!("bmp: {}", message))
+}
+
+fn word(data: &[u8], at: usize) -> io::Result<u32>
+{
+    data.get(at .. at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])).ok_or_else(|| invalid("unexpected end of file"))
+}
+
+// uncompressed 8-bit (palette), 24-bit & 32-bit windows bitmaps
+pub fn decode(data: &[u8]) -> io::Result<Bitmap>
+{
+    if data.len() < 54 || &data[0 .. 2] != b"BM" { return Err(invalid("invalid signature")); }
+
+    let offset: usize = word(data, 10)? as usize;
+    let header_size: usize = word(data, 14)? as usize;
+
+    let signed_width: i32 = word(data, 18)? as i32;
+    let signed_height: i32 = word(data, 22)? as i32;
+    let bits: u16 = u16::from_le_bytes([data[28], data[29]]);
+    let compression: u32 = word(data, 30)?;
+
+    if signed_width <= 0 || signed_height == 0 { return Err(invalid("invalid dimensions")); }
+
+    let width: usize = signed_width as usize;
+
+    // a negative height means the rows are stored top-down
+    let height: usize = signed_height.unsigned_abs() as usize;
+
+    // 3 = bit fields, accepted for 32-bit images as long as they use the usual BGRA layout
+    if compression != 0 && !(compression == 3 && bits == 32) { return Err(invalid("compressed images are not supported")); }
+
+    let palette: Vec<u32> = if bits == 8
+    {
+        let mut colors: usize = word(data, 46)? as usize;
+
+        if colors == 0 { colors = 256; }
+
+        // the palette follows the info header, as BGR0 entries
+        (0 .. colors).map(|i|
+        {
+            let at: usize = 14 + header_size + i * 4;
+
+            data.get(at .. at + 3).map(|c| (c[2] as u32) << 24 | (c[1] as u32) << 16 | (c[0] as u32) << 8 | 0xFF).ok_or_else(|| invalid("unexpected end of file"))
+        })
+        .collect::<io::Result<Vec<u32>>>()?
+    }
+    else
+    {
+        Vec::new()
+    };
+
+    let size: usize = match bits
+    {
+        8 => 1,
+        24 => 3,
+        32 => 4,
+
+        _ => return Err(invalid("unsupported pixel depth")),
+    };
+
+    // rows are padded to a multiple of four bytes
+    let stride: usize = width.checked_mul(size).and_then(|bytes| bytes.checked_add(3)).ok_or_else(|| invalid("image too large"))? & !3;
+    let capacity: usize = width.checked_mul(height).ok_or_else(|| invalid("image too large"))?;
+
+    // so the row offsets below can't overflow
+    stride.checked_mul(height).and_then(|bytes| bytes.checked_add(offset)).ok_or_else(|| invalid("image too large"))?;
+
+    let mut pixels: Vec<u32> = Vec::with_capacity(capacity);
+
+    for row in 0 .. height
+    {
+        let y: usize = if signed_height > 0 { height - 1 - row } else { row };
+
+        let line: &[u8] = data.get(offset + y * stride .. offset + y * stride + width * size).ok_or_else(|| invalid("unexpected end of file"))?;
+
+        for p in line.chunks_exact(size)
+        {
+            pixels.push(match size
+            {
+                1 => *palette.get(p[0] as usize).ok_or_else(|| invalid("palette index out of range"))?,
+                _ => (p[2] as u32) << 24 | (p[1] as u32) << 16 | (p[0] as u32) << 8 | 0xFF,
+            });
+        }
+    }
+
+    Ok(Bitmap::create(width as i32, height as i32, pixels))
+}
@@ -0,0 +1,6 @@
+pub mod bitmap;
+pub mod ppm;
+pub mod tga;
+pub mod bmp;
+pub mod png;
+pub mod zlib;
@@ -0,0 +1,240 @@
+use super::{bitmap::*, zlib};
+
+use std::io;
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+pub fn crc32(data: &[u8]) -> u32
+{
+    let mut crc: u32 = 0xFFFFFFFF;
+
+    for byte in data
+    {
+        crc ^= *byte as u32;
+
+        for _ in 0 .. 8
+        {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+        }
+    }
+
+    !crc
+}
+
+fn chunk(data: &mut Vec<u8>, kind: &[u8; 4], content: &[u8])
+{
+    data.extend((content.len() as u32).to_be_bytes());
+
+    let start: usize = data.len();
+
+    data.extend(kind);
+    data.extend(content);
+
+    let crc: u32 = crc32(&data[start ..]);
+
+    data.extend(crc.to_be_bytes());
+}
+
+// 8-bit RGBA, non-interlaced
+pub fn encode(bitmap: &Bitmap) -> Vec<u8>
+{
+    let mut data: Vec<u8> = SIGNATURE.to_vec();
+
+    let mut header: Vec<u8> = Vec::new();
+
+    header.extend((bitmap.width as u32).to_be_bytes());
+    header.extend((bitmap.height as u32).to_be_bytes());
+
+    // bit depth, color type (RGBA), compression, filter & interlace method
+    header.extend([8, 6, 0, 0, 0]);
+
+    chunk(&mut data, b"IHDR", &header);
+
+    let rgba: Vec<u8> = bitmap.rgba();
+    let stride: usize = bitmap.width as usize * 4;
+
+    // every scanline is prefixed with its filter type, 'sub' (1) predicts each byte from its left neighbor
+    let mut scanlines: Vec<u8> = Vec::with_capacity(rgba.len() + bitmap.height as usize);
+
+    for row in rgba.chunks(stride.max(1))
+    {
+        scanlines.push(1);
+
+        for i in 0 .. row.len()
+        {
+            let left: u8 = if i >= 4 { row[i - 4] } else { 0 };
+
+            scanlines.push(row[i].wrapping_sub(left));
+        }
+    }
+
+    chunk(&mut data, b"IDAT", &zlib::compress(&scanlines));
+    chunk(&mut data, b"IEND", &[]);
+
+    data
+}
+
+fn invalid(message: &str) -> io::Error
+{
+    io::Error::new(io::ErrorKind::InvalidData, format!("png: {}", message))
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8
+{
+    let p: i16 = a as i16 + b as i16 - c as i16;
+
+    let pa: i16 = (p - a as i16).abs();
+    let pb: i16 = (p - b as i16).abs();
+    let pc: i16 = (p - c as i16).abs();
+
+    if pa <= pb && pa <= pc { a } else if pb <= pc { b } else { c }
+}
+
+// non-interlaced grayscale, RGB, palette, grayscale + alpha & RGBA images
+pub fn decode(data: &[u8]) -> io::Result<Bitmap>
+{
+    if data.len() < 8 || data[0 .. 8] != SIGNATURE { return Err(invalid("invalid signature")); }
+
+    let mut position: usize = 8;
+
+    let mut header: Option<&[u8]> = None;
+    let mut palette: Vec<u32> = Vec::new();
+    let mut compressed: Vec<u8> = Vec::new();
+
+    while position + 8 <= data.len()
+    {
+        let length: usize = u32::from_be_bytes([data[position], data[position + 1], data[position + 2], data[position + 3]]) as usize;
+        let kind: &[u8] = &data[position + 4 .. position + 8];
+        let content: &[u8] = data.get(position + 8 .. position + 8 + length).ok_or_else(|| invalid("unexpected end of file"))?;
+
+        match kind
+        {
+            b"IHDR" => header = Some(content),
+            b"PLTE" => palette = content.chunks_exact(3).map(|c| (c[0] as u32) << 24 | (c[1] as u32) << 16 | (c[2] as u32) << 8 | 0xFF).collect(),
+
+            // palette transparency
+            b"tRNS" =>
+            {
+                for (entry, alpha) in palette.iter_mut().zip(content)
+                {
+                    *entry = (*entry & 0xFFFFFF00) | *alpha as u32;
+                }
+            }
+
+            b"IDAT" => compressed.extend(content),
+            b"IEND" => break,
+
+            _ => { }
+        }
+
+        // chunk length, type, content & crc
+        position += 12 + length;
+    }
+
+    let header: &[u8] = header.filter(|h| h.len() >= 13).ok_or_else(|| invalid("missing header"))?;
+
+    let width: usize = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+    let height: usize = u32::from_be_bytes([header[4], header[5], header[6], header[7]]) as usize;
+
+    let depth: usize = header[8] as usize;
+    let color_type: u8 = header[9];
+
+    if header[12] != 0 { return Err(invalid("interlaced images are not supported")); }
+
+    let channels: usize = match color_type
+    {
+        0 | 3 => 1,
+        2 => 3,
+        4 => 2,
+        6 => 4,
+
+        _ => return Err(invalid("invalid color type")),
+    };
+
+    // palettes may pack several indices to a byte but can't go past 8 bits, the other types are 8 or 16 bits per sample
+    let supported: bool = match color_type
+    {
+        3 => matches!(depth, 1 | 2 | 4 | 8),
+        _ => matches!(depth, 8 | 16),
+    };
+
+    if !supported { return Err(invalid("unsupported bit depth")); }
+
+    if width == 0 || height == 0 { return Err(invalid("empty image")); }
+    if width > i32::MAX as usize || height > i32::MAX as usize { return Err(invalid("image too large")); }
+
+    let raw: Vec<u8> = zlib::decompress(&compressed)?;
+
+    // bytes per complete pixel (at least one) & per scanline
+    let bpp: usize = usize::max(1, channels * depth / 8);
+    let stride: usize = (width * channels * depth).div_ceil(8);
+
+    let size: usize = (stride + 1).checked_mul(height).ok_or_else(|| invalid("image too large"))?;
+
+    if raw.len() < size { return Err(invalid("not enough image data")); }
+
+    let mut image: Vec<u8> = vec![0; stride * height];
+
+    for y in 0 .. height
+    {
+        let filter: u8 = raw[y * (stride + 1)];
+        let line: &[u8] = &raw[y * (stride + 1) + 1 .. (y + 1) * (stride + 1)];
+
+        let (previous, current) = image.split_at_mut(y * stride);
+
+        let above: &[u8] = if y > 0 { &previous[(y - 1) * stride ..] } else { &[] };
+        let current: &mut [u8] = &mut current[.. stride];
+
+        for i in 0 .. stride
+        {
+            let a: u8 = if i >= bpp { current[i - bpp] } else { 0 };
+            let b: u8 = if y > 0 { above[i] } else { 0 };
+            let c: u8 = if i >= bpp && y > 0 { above[i - bpp] } else { 0 };
+
+            current[i] = match filter
+            {
+                0 => line[i],
+                1 => line[i].wrapping_add(a),
+                2 => line[i].wrapping_add(b),
+                3 => line[i].wrapping_add(((a as u16 + b as u16) / 2) as u8),
+                4 => line[i].wrapping_add(paeth(a, b, c)),
+
+                _ => return Err(invalid("invalid filter type")),
+            };
+        }
+    }
+
+    let mut pixels: Vec<u32> = Vec::with_capacity(width * height);
+
+    for y in 0 .. height
+    {
+        let line: &[u8] = &image[y * stride .. (y + 1) * stride];
+
+        for x in 0 .. width
+        {
+            // most significant byte of every sample, 16-bit samples are truncated to 8 bits
+            let sample = |channel: usize| -> u32 { line[(x * channels + channel) * depth / 8] as u32 };
+
+            let pixel: u32 = match color_type
+            {
+                0 => sample(0) << 24 | sample(0) << 16 | sample(0) << 8 | 0xFF,
+                2 => sample(0) << 24 | sample(1) << 16 | sample(2) << 8 | 0xFF,
+                4 => sample(0) << 24 | sample(0) << 16 | sample(0) << 8 | sample(1),
+                6 => sample(0) << 24 | sample(1) << 16 | sample(2) << 8 | sample(3),
+
+                _ =>
+                {
+                    // palette indices may be packed several to a byte
+                    let bit: usize = x * depth;
+                    let index: usize = (line[bit / 8] as usize >> (8 - depth - bit % 8)) & ((1 << depth) - 1);
+
+                    *palette.get(index).ok_or_else(|| invalid("palette index out of range"))?
+                }
+            };
+
+            pixels.push(pixel);
+        }
+    }
+
+    Ok(Bitmap::create(width as i32, height as i32, pixels))
+}
@@ -0,0 +1,106 @@
+use super::bitmap::*;
+
+use std::io;
+
+// binary portable pixmap (P6)
+pub fn encode(bitmap: &Bitmap) -> Vec<u8>
+{
+    let mut data: Vec<u8> = format!("P6\n{} {}\n255\n", bitmap.width, bitmap.height).into_bytes();
+
+    data.extend(bitmap.rgb());
+
+    data
+}
+
+// reads the next whitespace separated header token, skipping comments
+fn token<'a>(data: &'a [u8], position: &mut usize) -> io::Result<&'a str>
+{
+    loop
+    {
+        while *position < data.len() && data[*position].is_ascii_whitespace() { *position += 1; }
+
+        if *position < data.len() && data[*position] == b'#'
+        {
+            while *position < data.len() && data[*position] != b'\n' { *position += 1; }
+        }
+        else
+        {
+            break;
+        }
+    }
+
+    let start: usize = *position;
+
+    while *position < data.len() && !data[*position].is_ascii_whitespace() { *position += 1; }
+
+    std::str::from_utf8(&data[start .. *position]).map_err(|_| invalid("malformed header"))
+}
+
+fn number(data: &[u8], position: &mut usize) -> io::Result<usize>
+{
+    token(data, position)?.parse().map_err(|_| invalid("malformed header"))
+}
+
+fn invalid(message: &str) -> io::Error
+{
+    io::Error::new(io::ErrorKind::InvalidData, format!("ppm: {}", message))
+}
+
+// binary (P6) or plain (P3) portable pixmap
+pub fn decode(data: &[u8]) -> io::Result<Bitmap>
+{
+    let mut position: usize = 0;
+
+    let magic: String = token(data, &mut position)?.to_string();
+
+    let width: usize = number(data, &mut position)?;
+    let height: usize = number(data, &mut position)?;
+    let max: usize = number(data, &mut position)?;
+
+    if max == 0 || max > 65535 { return Err(invalid("invalid maximum value")); }
+
+    if width == 0 || height == 0 { return Err(invalid("empty image")); }
+    if width > i32::MAX as usize || height > i32::MAX as usize { return Err(invalid("image too large")); }
+
+    let count: usize = width.checked_mul(height).and_then(|pixels| pixels.checked_mul(3)).ok_or_else(|| invalid("image too large"))?;
+
+    let samples: Vec<usize> = match magic.as_str()
+    {
+        "P6" =>
+        {
+            // a single whitespace character separates the header from the raster
+            position += 1;
+
+            let size: usize = if max > 255 { 2 } else { 1 };
+
+            let end: usize = count.checked_mul(size).and_then(|bytes| bytes.checked_add(position)).ok_or_else(|| invalid("image too large"))?;
+
+            let raster: &[u8] = data.get(position .. end).ok_or_else(|| invalid("unexpected end of file"))?;
+
+            if size == 2
+            {
+                raster.chunks_exact(2).map(|s| u16::from_be_bytes([s[0], s[1]]) as usize).collect()
+            }
+            else
+            {
+                raster.iter().map(|s| *s as usize).collect()
+            }
+        }
+
+        "P3" => (0 .. count).map(|_| number(data, &mut position)).collect::<io::Result<Vec<usize>>>()?,
+
+        _ => return Err(invalid("unsupported format")),
+    };
+
+    let pixels: Vec<u32> = samples.chunks_exact(3).map(|s|
+    {
+        let r: u32 = (s[0] * 255 / max) as u32;
+        let g: u32 = (s[1] * 255 / max) as u32;
+        let b: u32 = (s[2] * 255 / max) as u32;
+
+        (r << 24) | (g << 16) | (b << 8) | 0xFF
+    })
+    .collect();
+
+    Ok(Bitmap::create(width as i32, height as i32, pixels))
+}
@@ -0,0 +1,130 @@
+use super::bitmap::*;
+
+use std::io;
+
+// uncompressed true-color targa with an alpha channel
+pub fn encode(bitmap: &Bitmap) -> Vec<u8>
+{
+    let mut data: Vec<u8> = Vec::with_capacity(18 + bitmap.pixels.len() * 4);
+
+    // id length, color map type, image type (uncompressed true-color)
+    data.extend([0, 0, 2]);
+    // color map specification (unused)
+    data.extend([0; 5]);
+    // x & y origin
+    data.extend([0; 4]);
+
+    data.extend((bitmap.width as u16).to_le_bytes());
+    data.extend((bitmap.height as u16).to_le_bytes());
+
+    // bits per pixel, descriptor (8 alpha bits, top-left origin)
+    data.extend([32, 0x28]);
+
+    // pixels are stored as BGRA
+    for p in &bitmap.pixels
+    {
+        data.extend([(p >> 8) as u8, (p >> 16) as u8, (p >> 24) as u8, *p as u8]);
+    }
+
+    data
+}
+
+fn invalid(message: &str) -> io::Error
+{
+    io::Error::new(io::ErrorKind::InvalidData, format!("tga: {}", message))
+}
+
+// uncompressed & run-length encoded true-color (24/32-bit) or grayscale (8-bit) targa
+pub fn decode(data: &[u8]) -> io::Result<Bitmap>
+{
+    if data.len() < 18 { return Err(invalid("unexpected end of file")); }
+
+    let id_length: usize = data[0] as usize;
+    let color_map_type: u8 = data[1];
+    let image_type: u8 = data[2];
+
+    let width: usize = u16::from_le_bytes([data[12], data[13]]) as usize;
+    let height: usize = u16::from_le_bytes([data[14], data[15]]) as usize;
+    let bits: u8 = data[16];
+    let descriptor: u8 = data[17];
+
+    if width == 0 || height == 0 { return Err(invalid("empty image")); }
+
+    if color_map_type != 0 { return Err(invalid("color mapped images are not supported")); }
+
+    let (grayscale, compressed): (bool, bool) = match image_type
+    {
+        2 => (false, false),
+        3 => (true, false),
+        10 => (false, true),
+        11 => (true, true),
+
+        _ => return Err(invalid("unsupported image type")),
+    };
+
+    let size: usize = match (grayscale, bits)
+    {
+        (true, 8) => 1,
+        (false, 24) => 3,
+        (false, 32) => 4,
+
+        _ => return Err(invalid("unsupported pixel depth")),
+    };
+
+    let pixel = |p: &[u8]| -> u32
+    {
+        match size
+        {
+            1 => (p[0] as u32) << 24 | (p[0] as u32) << 16 | (p[0] as u32) << 8 | 0xFF,
+            3 => (p[2] as u32) << 24 | (p[1] as u32) << 16 | (p[0] as u32) << 8 | 0xFF,
+            _ => (p[2] as u32) << 24 | (p[1] as u32) << 16 | (p[0] as u32) << 8 | p[3] as u32,
+        }
+    };
+
+    let mut position: usize = 18 + id_length;
+    let mut pixels: Vec<u32> = Vec::with_capacity(width * height);
+
+    let mut next = |count: usize| -> io::Result<&[u8]>
+    {
+        let bytes: &[u8] = data.get(position .. position + count).ok_or_else(|| invalid("unexpected end of file"))?;
+
+        position += count;
+
+        Ok(bytes)
+    };
+
+    while pixels.len() < width * height
+    {
+        if compressed
+        {
+            // the packet header holds the run length & whether the packet repeats a single pixel
+            let header: u8 = next(1)?[0];
+            let count: usize = (header & 0x7F) as usize + 1;
+
+            if header & 0x80 != 0
+            {
+                let value: u32 = pixel(next(size)?);
+
+                pixels.extend(std::iter::repeat_n(value, count));
+            }
+            else
+            {
+                pixels.extend(next(size * count)?.chunks_exact(size).map(pixel));
+            }
+        }
+        else
+        {
+            pixels.extend(next(size * width * height)?.chunks_exact(size).map(pixel));
+        }
+    }
+
+    pixels.truncate(width * height);
+
+    // rows are stored bottom-up unless the descriptor says otherwise
+    if descriptor & 0x20 == 0
+    {
+        pixels = pixels.chunks(width.max(1)).rev().flatten().copied().collect();
+    }
+
+    Ok(Bitmap::create(width as i32, height as i32, pixels))
+}
@@ -0,0 +1,445 @@
+// minimal zlib (RFC 1950) streams: a fixed-Huffman deflate writer and a full inflate reader (RFC 1951)
+
+use std::io;
+
+const WINDOW: usize = 32768;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const MAX_CHAIN: usize = 64;
+const HASH_BITS: u32 = 15;
+
+// base lengths & extra bits for the length symbols 257 ..= 285
+pub const LENGTH_BASE: [u16; 29] = [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+pub const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+
+// base distances & extra bits for the distance symbols 0 ..= 29
+pub const DISTANCE_BASE: [u16; 30] = [1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577];
+pub const DISTANCE_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+
+struct BitWriter
+{
+    data: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl BitWriter
+{
+    // writes the lowest 'length' bits of 'value', least significant bit first
+    fn bits(&mut self, value: u32, length: u32)
+    {
+        self.buffer |= value << self.count;
+        self.count += length;
+
+        while self.count >= 8
+        {
+            self.data.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    // Huffman codes are packed starting with their most significant bit
+    fn code(&mut self, code: u32, length: u32)
+    {
+        self.bits(code.reverse_bits() >> (32 - length), length);
+    }
+
+    fn flush(&mut self)
+    {
+        if self.count > 0
+        {
+            self.data.push(self.buffer as u8);
+        }
+
+        self.buffer = 0;
+        self.count = 0;
+    }
+}
+
+pub fn adler32(data: &[u8]) -> u32
+{
+    let (mut a, mut b): (u32, u32) = (1, 0);
+
+    // 5552 is the largest block for which the sums can't overflow before the modulo
+    for chunk in data.chunks(5552)
+    {
+        for byte in chunk
+        {
+            a += *byte as u32;
+            b += a;
+        }
+
+        a %= 65521;
+        b %= 65521;
+    }
+
+    (b << 16) | a
+}
+
+fn literal(writer: &mut BitWriter, symbol: u32)
+{
+    match symbol
+    {
+        0 ..= 143 => writer.code(0x30 + symbol, 8),
+        144 ..= 255 => writer.code(0x190 + symbol - 144, 9),
+        256 ..= 279 => writer.code(symbol - 256, 7),
+        _ => writer.code(0xC0 + symbol - 280, 8),
+    }
+}
+
+fn copy(writer: &mut BitWriter, length: usize, distance: usize)
+{
+    let l: usize = LENGTH_BASE.iter().rposition(|base| *base as usize <= length).unwrap();
+
+    literal(writer, 257 + l as u32);
+    writer.bits((length - LENGTH_BASE[l] as usize) as u32, LENGTH_EXTRA[l] as u32);
+
+    let d: usize = DISTANCE_BASE.iter().rposition(|base| *base as usize <= distance).unwrap();
+
+    writer.code(d as u32, 5);
+    writer.bits((distance - DISTANCE_BASE[d] as usize) as u32, DISTANCE_EXTRA[d] as u32);
+}
+
+fn hash(data: &[u8], i: usize) -> usize
+{
+    let key: u32 = (data[i] as u32) << 16 | (data[i + 1] as u32) << 8 | data[i + 2] as u32;
+
+    (key.wrapping_mul(2654435761) >> (32 - HASH_BITS)) as usize
+}
+
+pub fn compress(data: &[u8]) -> Vec<u8>
+{
+    let mut writer = BitWriter { data: vec![0x78, 0x01], buffer: 0, count: 0 };
+
+    // single final block compressed with the fixed Huffman codes
+    writer.bits(1, 1);
+    writer.bits(1, 2);
+
+    // most recent position of every hash & the previous position with the same hash
+    let mut head: Vec<usize> = vec![usize::MAX; 1 << HASH_BITS];
+    let mut previous: Vec<usize> = vec![usize::MAX; WINDOW];
+
+    let mut i: usize = 0;
+
+    while i < data.len()
+    {
+        let mut best_length: usize = 0;
+        let mut best_distance: usize = 0;
+
+        if i + MIN_MATCH <= data.len()
+        {
+            let h: usize = hash(data, i);
+
+            let mut candidate: usize = head[h];
+            let mut chain: usize = 0;
+
+            while candidate != usize::MAX && i - candidate <= WINDOW && chain < MAX_CHAIN
+            {
+                let limit: usize = usize::min(MAX_MATCH, data.len() - i);
+                let length: usize = (0 .. limit).take_while(|k| data[candidate + k] == data[i + k]).count();
+
+                if length > best_length
+                {
+                    best_length = length;
+                    best_distance = i - candidate;
+
+                    if length == limit { break; }
+                }
+
+                candidate = previous[candidate % WINDOW];
+                chain += 1;
+            }
+        }
+
+        let advance: usize = if best_length >= MIN_MATCH { best_length } else { 1 };
+
+        if best_length >= MIN_MATCH
+        {
+            copy(&mut writer, best_length, best_distance);
+        }
+        else
+        {
+            literal(&mut writer, data[i] as u32);
+        }
+
+        // register every position that was consumed so later matches can refer back to it
+        for j in i .. i + advance
+        {
+            if j + MIN_MATCH <= data.len()
+            {
+                let h: usize = hash(data, j);
+
+                previous[j % WINDOW] = head[h];
+                head[h] = j;
+            }
+        }
+
+        i += advance;
+    }
+
+    // end of block
+    literal(&mut writer, 256);
+    writer.flush();
+
+    writer.data.extend(adler32(data).to_be_bytes());
+
+    writer.data
+}
+
+struct BitReader<'a>
+{
+    data: &'a [u8],
+    position: usize,
+    buffer: u32,
+    count: u32,
+}
+
+impl BitReader<'_>
+{
+    fn bits(&mut self, length: u32) -> io::Result<u32>
+    {
+        while self.count < length
+        {
+            let byte: u8 = *self.data.get(self.position).ok_or_else(|| invalid("unexpected end of stream"))?;
+
+            self.buffer |= (byte as u32) << self.count;
+            self.position += 1;
+            self.count += 8;
+        }
+
+        let value: u32 = self.buffer & ((1u64 << length) - 1) as u32;
+
+        self.buffer >>= length;
+        self.count -= length;
+
+        Ok(value)
+    }
+
+    // stored blocks start at a byte boundary
+    fn align(&mut self)
+    {
+        self.buffer = 0;
+        self.count = 0;
+    }
+}
+
+// canonical Huffman code, described by the number of codes of every length & the symbols sorted by code
+struct Huffman
+{
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman
+{
+    fn create(lengths: &[u8]) -> Self
+    {
+        let mut counts: [u16; 16] = [0; 16];
+
+        for length in lengths
+        {
+            counts[*length as usize] += 1;
+        }
+
+        counts[0] = 0;
+
+        let mut offsets: [u16; 16] = [0; 16];
+
+        for i in 1 .. 15
+        {
+            offsets[i + 1] = offsets[i] + counts[i];
+        }
+
+        let mut symbols: Vec<u16> = vec![0; lengths.len()];
+
+        for (symbol, length) in lengths.iter().enumerate()
+        {
+            if *length != 0
+            {
+                symbols[offsets[*length as usize] as usize] = symbol as u16;
+                offsets[*length as usize] += 1;
+            }
+        }
+
+        Self { counts, symbols }
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> io::Result<u16>
+    {
+        // first code of the current length & index of its symbol
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+
+        for length in 1 .. 16
+        {
+            code |= reader.bits(1)? as i32;
+
+            let count: i32 = self.counts[length] as i32;
+
+            if code - first < count
+            {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err(invalid("invalid Huffman code"))
+    }
+}
+
+fn invalid(message: &str) -> io::Error
+{
+    io::Error::new(io::ErrorKind::InvalidData, format!("zlib: {}", message))
+}
+
+fn fixed_codes() -> (Huffman, Huffman)
+{
+    let mut lengths: [u8; 288] = [0; 288];
+
+    lengths[0 .. 144].fill(8);
+    lengths[144 .. 256].fill(9);
+    lengths[256 .. 280].fill(7);
+    lengths[280 .. 288].fill(8);
+
+    (Huffman::create(&lengths), Huffman::create(&[5; 30]))
+}
+
+fn dynamic_codes(reader: &mut BitReader) -> io::Result<(Huffman, Huffman)>
+{
+    // order in which the code length code lengths are stored
+    const ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+    let literals: usize = reader.bits(5)? as usize + 257;
+    let distances: usize = reader.bits(5)? as usize + 1;
+    let codes: usize = reader.bits(4)? as usize + 4;
+
+    let mut code_lengths: [u8; 19] = [0; 19];
+
+    for i in ORDER.iter().take(codes)
+    {
+        code_lengths[*i] = reader.bits(3)? as u8;
+    }
+
+    let lengths_code: Huffman = Huffman::create(&code_lengths);
+
+    let mut lengths: Vec<u8> = Vec::with_capacity(literals + distances);
+
+    while lengths.len() < literals + distances
+    {
+        let symbol: u16 = lengths_code.decode(reader)?;
+
+        let (value, repeat): (u8, u32) = match symbol
+        {
+            0 ..= 15 => (symbol as u8, 1),
+            16 => (*lengths.last().ok_or_else(|| invalid("repeat without a previous length"))?, 3 + reader.bits(2)?),
+            17 => (0, 3 + reader.bits(3)?),
+            _ => (0, 11 + reader.bits(7)?),
+        };
+
+        lengths.extend(std::iter::repeat_n(value, repeat as usize));
+    }
+
+    if lengths.len() > literals + distances { return Err(invalid("too many code lengths")); }
+
+    Ok((Huffman::create(&lengths[.. literals]), Huffman::create(&lengths[literals ..])))
+}
+
+fn inflate_block(reader: &mut BitReader, output: &mut Vec<u8>, literal_code: &Huffman, distance_code: &Huffman) -> io::Result<()>
+{
+    loop
+    {
+        let symbol: usize = literal_code.decode(reader)? as usize;
+
+        match symbol
+        {
+            0 ..= 255 => output.push(symbol as u8),
+
+            256 => return Ok(()),
+
+            257 ..= 285 =>
+            {
+                let length: usize = LENGTH_BASE[symbol - 257] as usize + reader.bits(LENGTH_EXTRA[symbol - 257] as u32)? as usize;
+
+                let d: usize = distance_code.decode(reader)? as usize;
+
+                if d >= 30 { return Err(invalid("invalid distance symbol")); }
+
+                let distance: usize = DISTANCE_BASE[d] as usize + reader.bits(DISTANCE_EXTRA[d] as u32)? as usize;
+
+                if distance > output.len() { return Err(invalid("distance too far back")); }
+
+                // the source & destination may overlap, so the bytes are copied one at a time
+                for _ in 0 .. length
+                {
+                    output.push(output[output.len() - distance]);
+                }
+            }
+
+            _ => return Err(invalid("invalid literal/length symbol")),
+        }
+    }
+}
+
+pub fn decompress(data: &[u8]) -> io::Result<Vec<u8>>
+{
+    if data.len() < 6 || data[0] & 0x0F != 8 || !((data[0] as u16) << 8 | data[1] as u16).is_multiple_of(31)
+    {
+        return Err(invalid("invalid header"));
+    }
+
+    if data[1] & 0x20 != 0 { return Err(invalid("preset dictionaries are not supported")); }
+
+    let mut reader = BitReader { data: &data[2 ..], position: 0, buffer: 0, count: 0 };
+    let mut output: Vec<u8> = Vec::new();
+
+    loop
+    {
+        let last: bool = reader.bits(1)? == 1;
+
+        match reader.bits(2)?
+        {
+            // stored
+            0 =>
+            {
+                reader.align();
+
+                let header: &[u8] = reader.data.get(reader.position .. reader.position + 4).ok_or_else(|| invalid("unexpected end of stream"))?;
+
+                let length: usize = u16::from_le_bytes([header[0], header[1]]) as usize;
+
+                if length != !u16::from_le_bytes([header[2], header[3]]) as usize { return Err(invalid("corrupt stored block")); }
+
+                reader.position += 4;
+
+                output.extend(reader.data.get(reader.position .. reader.position + length).ok_or_else(|| invalid("unexpected end of stream"))?);
+
+                reader.position += length;
+            }
+
+            1 =>
+            {
+                let (literal_code, distance_code) = fixed_codes();
+
+                inflate_block(&mut reader, &mut output, &literal_code, &distance_code)?;
+            }
+
+            2 =>
+            {
+                let (literal_code, distance_code) = dynamic_codes(&mut reader)?;
+
+                inflate_block(&mut reader, &mut output, &literal_code, &distance_code)?;
+            }
+
+            _ => return Err(invalid("invalid block type")),
+        }
+
+        if last { break; }
+    }
+
+    Ok(output)
+}
@@ -0,0 +1,9 @@
+#![allow(clippy::upper_case_acronyms)]
+
+pub mod algebra;
+pub mod topology;
+pub mod video;
+pub mod image;
+
+#[cfg(test)]
+mod tests;
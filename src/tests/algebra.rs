@@ -0,0 +1,319 @@
+use crate::algebra::{*, matrix3x3::*, matrix4x4::*, quaternion::*, vector2::*, vector3::*, vector4::*};
+
+fn close(a: &Vector3, b: &Vector3) -> bool
+{
+    (*a - *b).length() < 1e-5
+}
+
+fn same_matrix(a: &Matrix4x4, b: &Matrix4x4) -> bool
+{
+    (0 .. 4).all(|row| (0 .. 4).all(|column| (a.get(row, column) - b.get(row, column)).abs() < 1e-5))
+}
+
+// the rotated direction, as a matrix would transform a normal
+fn apply(matrix: &Matrix4x4, v: &Vector3) -> Vector3
+{
+    Vector3::create
+    (
+        matrix.get(0, 0) * v.x + matrix.get(0, 1) * v.y + matrix.get(0, 2) * v.z,
+        matrix.get(1, 0) * v.x + matrix.get(1, 1) * v.y + matrix.get(1, 2) * v.z,
+        matrix.get(2, 0) * v.x + matrix.get(2, 1) * v.y + matrix.get(2, 2) * v.z,
+    )
+}
+
+#[test]
+fn approximate_equality()
+{
+    assert!(approximately(1.0, 1.0 + 1e-6));
+    assert!(!approximately(1.0, 1.001));
+
+    // relative past 1
+    assert!(approximately(100000.0, 100000.5));
+
+    assert_eq!(Vector3::create(0.1 + 0.2, 1.0, 0.0), Vector3::create(0.3, 1.0, 0.0));
+    assert_ne!(Vector3::create(0.0, 1.0, 0.0), Vector3::create(0.0, 1.01, 0.0));
+}
+
+#[test]
+fn vector2()
+{
+    let a: Vector2 = Vector2::create(3.0, 4.0);
+    let b: Vector2 = Vector2::create(1.0, -2.0);
+
+    assert_eq!(a.length(), 5.0);
+    assert_eq!(a.normalized(), Vector2::create(0.6, 0.8));
+    assert_eq!(Vector2::dot(&a, &b), -5.0);
+
+    assert_eq!(a + b, Vector2::create(4.0, 2.0));
+    assert_eq!(a - b, Vector2::create(2.0, 6.0));
+    assert_eq!(a * b, Vector2::create(3.0, -8.0));
+    assert_eq!(a * 2.0, Vector2::create(6.0, 8.0));
+    assert_eq!(a / 2.0, Vector2::create(1.5, 2.0));
+    assert_eq!(-a, a.opposite());
+}
+
+#[test]
+fn vector3()
+{
+    let x: Vector3 = Vector3::create(1.0, 0.0, 0.0);
+    let y: Vector3 = Vector3::create(0.0, 1.0, 0.0);
+
+    assert_eq!(Vector3::cross(&x, &y), Vector3::create(0.0, 0.0, 1.0));
+    assert_eq!(Vector3::dot(&x, &y), 0.0);
+
+    // mirrored around the normal
+    assert_eq!(Vector3::reflect(&Vector3::create(1.0, -1.0, 0.0), &y), Vector3::create(1.0, 1.0, 0.0).normalized());
+
+    assert_eq!(-x, Vector3::create(-1.0, 0.0, 0.0));
+}
+
+#[test]
+fn vector4()
+{
+    let point: Vector4 = Vector4::extended(&Vector3::create(1.0, 2.0, 3.0), 1.0);
+
+    assert_eq!(point.xyz(), Vector3::create(1.0, 2.0, 3.0));
+    assert_eq!((point * 2.0).projected(), Vector3::create(1.0, 2.0, 3.0));
+    assert_eq!(Vector4::dot(&point, &point), 15.0);
+    assert_eq!(Vector4::create(0.0, 0.0, 3.0, 4.0).length(), 5.0);
+    assert_eq!(point - point, Vector4::zero());
+}
+
+#[test]
+fn assignment_operators()
+{
+    let mut v: Vector3 = Vector3::create(1.0, 2.0, 3.0);
+
+    v += Vector3::create(1.0, 1.0, 1.0);
+    assert_eq!(v, Vector3::create(2.0, 3.0, 4.0));
+
+    v -= Vector3::create(2.0, 2.0, 2.0);
+    assert_eq!(v, Vector3::create(0.0, 1.0, 2.0));
+
+    v *= 3.0;
+    assert_eq!(v, Vector3::create(0.0, 3.0, 6.0));
+
+    v /= 3.0;
+    assert_eq!(v, Vector3::create(0.0, 1.0, 2.0));
+
+    let mut m: Matrix4x4 = Matrix4x4::translate(1.0, 0.0, 0.0);
+
+    m *= Matrix4x4::scale(2.0, 2.0, 2.0);
+    assert_eq!(m, Matrix4x4::translate(1.0, 0.0, 0.0) * Matrix4x4::scale(2.0, 2.0, 2.0));
+}
+
+#[test]
+fn lerp_and_clamp()
+{
+    assert_eq!(lerp(2.0, 4.0, 0.25), 2.5);
+
+    let a: Vector3 = Vector3::create(0.0, 10.0, -2.0);
+    let b: Vector3 = Vector3::create(4.0, 20.0, 2.0);
+
+    assert_eq!(Vector3::lerp(&a, &b, 0.5), Vector3::create(2.0, 15.0, 0.0));
+    assert_eq!(Vector2::lerp(&Vector2::zero(), &Vector2::create(2.0, 4.0), 1.0), Vector2::create(2.0, 4.0));
+    assert_eq!(Vector4::lerp(&Vector4::zero(), &Vector4::create(2.0, 4.0, 6.0, 8.0), 0.5), Vector4::create(1.0, 2.0, 3.0, 4.0));
+
+    assert_eq!(b.clamp(0.0, 3.0), Vector3::create(3.0, 3.0, 2.0));
+    assert_eq!(a.clamp(-1.0, 1.0), Vector3::create(0.0, 1.0, -1.0));
+}
+
+#[test]
+fn matrix_vector_product()
+{
+    let m: Matrix4x4 = Matrix4x4::translate(1.0, 2.0, 3.0) * Matrix4x4::scale(2.0, 2.0, 2.0);
+
+    // points are moved, directions are not
+    assert_eq!(m * Vector4::create(1.0, 1.0, 1.0, 1.0), Vector4::create(3.0, 4.0, 5.0, 1.0));
+    assert_eq!(m * Vector4::create(1.0, 1.0, 1.0, 0.0), Vector4::create(2.0, 2.0, 2.0, 0.0));
+
+    assert_eq!(m.upper() * Vector3::create(1.0, 0.0, 0.0), Vector3::create(2.0, 0.0, 0.0));
+}
+
+#[test]
+fn transpose()
+{
+    let m: Matrix4x4 = Matrix4x4::translate(1.0, 2.0, 3.0);
+
+    assert_eq!(m.transpose().get(3, 0), 1.0);
+    assert_eq!(m.transpose().transpose(), m);
+
+    // the inverse of a rotation is its transpose
+    let r: Matrix4x4 = Matrix4x4::rotate(&Vector3::create(10.0, 20.0, 30.0), Order::XYZ);
+
+    assert_eq!(r.transpose() * r, Matrix4x4::identity());
+    assert_eq!(r.upper().transpose(), r.transpose().upper());
+}
+
+#[test]
+fn determinant()
+{
+    assert_eq!(Matrix4x4::identity().determinant(), 1.0);
+    assert_eq!(Matrix4x4::scale(2.0, 3.0, 4.0).determinant(), 24.0);
+
+    // rotations & translations keep the volume
+    let m: Matrix4x4 = Matrix4x4::translate(5.0, 6.0, 7.0) * Matrix4x4::rotate(&Vector3::create(10.0, 20.0, 30.0), Order::ZYX);
+
+    assert!(approximately(m.determinant(), 1.0));
+    assert!(approximately(m.upper().determinant(), 1.0));
+
+    // a mirror flips the sign
+    assert_eq!(Matrix4x4::scale(-1.0, 1.0, 1.0).determinant(), -1.0);
+    assert_eq!(Matrix3x3::create([[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]]).determinant(), -1.0);
+
+    assert_eq!(Matrix4x4::scale(1.0, 0.0, 1.0).determinant(), 0.0);
+}
+
+#[test]
+fn inverse()
+{
+    let m: Matrix4x4 = Matrix4x4::translate(1.0, -2.0, 3.0) * Matrix4x4::rotate(&Vector3::create(30.0, 45.0, 60.0), Order::YXZ) * Matrix4x4::scale(2.0, 0.5, 3.0);
+
+    let inverse: Matrix4x4 = m.inverse().unwrap();
+
+    assert_eq!(m * inverse, Matrix4x4::identity());
+    assert_eq!(inverse * m, Matrix4x4::identity());
+
+    let point: Vector4 = Vector4::create(4.0, 5.0, 6.0, 1.0);
+
+    assert_eq!(inverse * (m * point), point);
+
+    // needs a row swap to find a pivot
+    let swapped: Matrix3x3 = Matrix3x3::create([[0.0, 2.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 4.0]]);
+
+    assert_eq!(swapped * swapped.inverse().unwrap(), Matrix3x3::identity());
+    assert_eq!(m.upper().inverse().unwrap(), inverse.upper());
+
+    // the perspective projection is invertible too
+    let projection: Matrix4x4 = Matrix4x4::perspective(75.0, 4.0 / 3.0, 0.1, 100.0);
+
+    assert_eq!(projection * projection.inverse().unwrap(), Matrix4x4::identity());
+
+    // singular matrices have none
+    assert!(Matrix4x4::scale(1.0, 0.0, 1.0).inverse().is_none());
+    assert!(Matrix3x3::create([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 0.0, 1.0]]).inverse().is_none());
+}
+
+// a model in millimeters, the determinant is tiny without the matrix being singular
+#[test]
+fn small_scale_inverse()
+{
+    let m: Matrix4x4 = Matrix4x4::scale(0.004, 0.004, 0.002);
+
+    let inverse: Matrix4x4 = m.inverse().unwrap();
+
+    assert_eq!(inverse, Matrix4x4::scale(250.0, 250.0, 500.0));
+    assert_eq!(m.upper().inverse().unwrap(), inverse.upper());
+
+    assert!(Matrix4x4::scale(0.004, 0.0, 0.002).inverse().is_none());
+    assert!(Matrix4x4::scale(0.004, 0.0, 0.002).upper().inverse().is_none());
+}
+
+#[test]
+fn axis_rotations()
+{
+    let (x, y, z) = (Vector3::create(1.0, 0.0, 0.0), Vector3::create(0.0, 1.0, 0.0), Vector3::create(0.0, 0.0, 1.0));
+
+    // counter-clockwise when looking down the axis
+    assert!(close(&apply(&Matrix4x4::rotate_x(90.0), &y), &z));
+    assert!(close(&apply(&Matrix4x4::rotate_y(90.0), &z), &x));
+    assert!(close(&apply(&Matrix4x4::rotate_z(90.0), &x), &y));
+}
+
+#[test]
+fn euler_order()
+{
+    let angles: Vector3 = Vector3::create(90.0, 90.0, 0.0);
+    let v: Vector3 = Vector3::create(0.0, 1.0, 0.0);
+
+    // y goes to z around x, then to x around y
+    assert!(close(&apply(&Matrix4x4::rotate(&angles, Order::XYZ), &v), &Vector3::create(1.0, 0.0, 0.0)));
+
+    // y is left alone by the rotation around y, then goes to z around x
+    assert!(close(&apply(&Matrix4x4::rotate(&angles, Order::YXZ), &v), &Vector3::create(0.0, 0.0, 1.0)));
+}
+
+#[test]
+fn quaternion_matches_matrices()
+{
+    let angles: Vector3 = Vector3::create(30.0, -45.0, 60.0);
+
+    for order in [Order::XYZ, Order::XZY, Order::YXZ, Order::YZX, Order::ZXY, Order::ZYX]
+    {
+        let q: Quaternion = Quaternion::euler(&angles, order);
+
+        assert!(same_matrix(&q.matrix(), &Matrix4x4::rotate(&angles, order)));
+    }
+
+    let axis: Vector3 = Vector3::create(0.0, 0.0, 1.0);
+
+    assert!(same_matrix(&Quaternion::axis_angle(&axis, 35.0).matrix(), &Matrix4x4::rotate_z(35.0)));
+}
+
+#[test]
+fn quaternion_rotation()
+{
+    let q: Quaternion = Quaternion::axis_angle(&Vector3::create(1.0, 1.0, 1.0), 120.0);
+
+    // a third of a turn around the diagonal cycles the axes
+    assert!(close(&q.rotate(&Vector3::create(1.0, 0.0, 0.0)), &Vector3::create(0.0, 1.0, 0.0)));
+
+    let (axis, angle) = q.to_axis_angle();
+
+    assert!(close(&axis, &Vector3::create(1.0, 1.0, 1.0).normalized()));
+    assert!((angle - 120.0).abs() < 1e-3);
+
+    // the conjugate undoes the rotation
+    let v: Vector3 = Vector3::create(0.3, -2.0, 5.0);
+
+    assert!(close(&q.conjugate().rotate(&q.rotate(&v)), &v));
+}
+
+#[test]
+fn slerp()
+{
+    let axis: Vector3 = Vector3::create(0.0, 1.0, 0.0);
+
+    let a: Quaternion = Quaternion::axis_angle(&axis, 10.0);
+    let b: Quaternion = Quaternion::axis_angle(&axis, 110.0);
+
+    let (_, angle) = Quaternion::slerp(&a, &b, 0.25).to_axis_angle();
+
+    assert!((angle - 35.0).abs() < 1e-3);
+
+    // -b is the same rotation, the shortest path doesn't change
+    let negated: Quaternion = Quaternion::create(-b.w, -b.x, -b.y, -b.z);
+
+    assert!(same_matrix(&Quaternion::slerp(&a, &negated, 0.5).matrix(), &Matrix4x4::rotate_y(60.0)));
+
+    // the end points are exact
+    assert!(same_matrix(&Quaternion::slerp(&a, &b, 1.0).matrix(), &b.matrix()));
+}
+
+#[test]
+fn frustum()
+{
+    // a centered frustum is the usual perspective
+    let (near, far) = (0.5, 50.0);
+    let top: f32 = near * f32::tan(f32::to_radians(30.0));
+
+    assert_eq!(Matrix4x4::frustum(-top * 2.0, top * 2.0, -top, top, near, far), Matrix4x4::perspective(60.0, 2.0, near, far));
+
+    // the corners of an off-center one land on the corners of clip space, at depth 0 on the near plane & 1 on the far plane
+    let m: Matrix4x4 = Matrix4x4::frustum(0.0, 2.0, -1.0, 3.0, 1.0, 10.0);
+
+    assert_eq!((m * Vector4::create(0.0, -1.0, 1.0, 1.0)).projected(), Vector3::create(-1.0, -1.0, 0.0));
+    assert_eq!((m * Vector4::create(20.0, 30.0, 10.0, 1.0)).projected(), Vector3::create(1.0, 1.0, 1.0));
+}
+
+#[test]
+fn orthographic()
+{
+    let m: Matrix4x4 = Matrix4x4::orthographic(-4.0, 2.0, -1.0, 1.0, 1.0, 11.0);
+
+    assert_eq!(m * Vector4::create(-4.0, -1.0, 1.0, 1.0), Vector4::create(-1.0, -1.0, 0.0, 1.0));
+    assert_eq!(m * Vector4::create(2.0, 1.0, 11.0, 1.0), Vector4::create(1.0, 1.0, 1.0, 1.0));
+
+    // the distance doesn't change the size
+    assert_eq!(m * Vector4::create(-1.0, 0.0, 6.0, 1.0), Vector4::create(0.0, 0.0, 0.5, 1.0));
+}
@@ -0,0 +1,80 @@
+use crate::image::bitmap::*;
+
+use std::{env, fs, path::PathBuf};
+
+// largest difference allowed in a single color channel (out of 255)
+const TOLERANCE: u32 = 2;
+
+// fraction of the pixels that may exceed the tolerance, absorbs rounding noise along triangle edges
+const MAX_MISMATCH: f32 = 0.002;
+
+fn reference_path(name: &str) -> PathBuf
+{
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets/golden").join(format!("{}.ppm", name))
+}
+
+fn output_path(name: &str, suffix: &str) -> PathBuf
+{
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/golden").join(format!("{}.{}.png", name, suffix))
+}
+
+fn channel_difference(a: u32, b: u32) -> u32
+{
+    (1 ..= 3).map(|i| (((a >> (i * 8)) & 0xFF) as i32 - ((b >> (i * 8)) & 0xFF) as i32).unsigned_abs()).max().unwrap()
+}
+
+// compares a rendered image against its stored reference
+//
+// run with GOLDEN_UPDATE=1 to (re)create the references after an intended change in the output,
+// on failure the rendered image and a diff (mismatches in red) are written to target/golden/
+pub fn compare(name: &str, actual: &Bitmap)
+{
+    let reference: PathBuf = reference_path(name);
+
+    if env::var_os("GOLDEN_UPDATE").is_some()
+    {
+        actual.save(&reference).unwrap();
+
+        return;
+    }
+
+    let expected: Bitmap = match Bitmap::load(&reference)
+    {
+        Ok(expected) => expected,
+        Err(error) => panic!("missing reference image {} ({}), run the tests with GOLDEN_UPDATE=1 to create it", reference.display(), error),
+    };
+
+    assert!(expected.width == actual.width && expected.height == actual.height, "'{}' is {}x{} but the reference is {}x{}", name, actual.width, actual.height, expected.width, expected.height);
+
+    let mut mismatches: usize = 0;
+
+    let diff: Vec<u32> = expected.pixels.iter().zip(&actual.pixels).map(|(e, a)|
+    {
+        if channel_difference(*e, *a) > TOLERANCE
+        {
+            mismatches += 1;
+
+            0xFF0000FF
+        }
+        else
+        {
+            // dimmed grayscale of the reference, so the mismatches stand out
+            let level: u32 = (((e >> 24) & 0xFF) + ((e >> 16) & 0xFF) + ((e >> 8) & 0xFF)) / 9;
+
+            (level << 24) | (level << 16) | (level << 8) | 0xFF
+        }
+    })
+    .collect();
+
+    let ratio: f32 = mismatches as f32 / actual.pixels.len() as f32;
+
+    if ratio > MAX_MISMATCH
+    {
+        fs::create_dir_all(output_path(name, "").parent().unwrap()).unwrap();
+
+        actual.save(output_path(name, "actual")).unwrap();
+        Bitmap::create(actual.width, actual.height, diff).save(output_path(name, "diff")).unwrap();
+
+        panic!("'{}' differs from its reference in {} pixels ({:.2}%), see {}", name, mismatches, ratio * 100.0, output_path(name, "diff").display());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::image::{bitmap::*, bmp, png, ppm, tga, zlib};
+
+fn asset(name: &str) -> String
+{
+    format!("{}/assets/golden/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn gradient() -> Bitmap
+{
+    let pixels: Vec<u32> = (0 .. 24 * 16).map(|i: u32| ((i * 7) & 0xFF) << 24 | ((i / 24 * 16) & 0xFF) << 16 | ((i * 3) & 0xFF) << 8 | 0xFF).collect();
+
+    Bitmap::create(24, 16, pixels)
+}
+
+#[test]
+fn zlib_round_trip()
+{
+    let data: Vec<u8> = (0 .. 100000u32).map(|i| ((i / 7) ^ (i % 13)) as u8).collect();
+
+    assert_eq!(zlib::decompress(&zlib::compress(&data)).unwrap(), data);
+    assert_eq!(zlib::decompress(&zlib::compress(&[])).unwrap(), Vec::<u8>::new());
+}
+
+#[test]
+fn encoders_round_trip()
+{
+    let bitmap: Bitmap = gradient();
+
+    assert_eq!(ppm::decode(&ppm::encode(&bitmap)).unwrap().pixels, bitmap.pixels);
+    assert_eq!(tga::decode(&tga::encode(&bitmap)).unwrap().pixels, bitmap.pixels);
+    assert_eq!(png::decode(&png::encode(&bitmap)).unwrap().pixels, bitmap.pixels);
+}
+
+// the assets were written by other encoders (dynamic Huffman codes & every PNG filter, RLE targa, bottom-up bitmap)
+#[test]
+fn decoders_agree()
+{
+    let reference: Bitmap = Bitmap::load(asset("checker.ppm")).unwrap();
+
+    for name in ["checker.png", "checker.tga", "checker.bmp"]
+    {
+        let bitmap: Bitmap = Bitmap::load(asset(name)).unwrap();
+
+        assert!(bitmap.width == reference.width && bitmap.height == reference.height, "{} has the wrong size", name);
+        assert!(bitmap.pixels == reference.pixels, "{} decoded differently", name);
+    }
+}
+
+#[test]
+fn corrupt_data_is_an_error()
+{
+    let mut data: Vec<u8> = png::encode(&gradient());
+
+    data.truncate(data.len() / 2);
+
+    assert!(png::decode(&data).is_err());
+    assert!(zlib::decompress(&[0x78, 0x01, 0xFF]).is_err());
+    assert!(Bitmap::load(asset("cube.obj")).is_err());
+}
+
+// headers that used to overflow or index out of bounds instead of failing
+#[test]
+fn malformed_headers_are_errors()
+{
+    let png_header = |width: u32, depth: u8, color_type: u8| -> Vec<u8>
+    {
+        let mut data: Vec<u8> = png::encode(&Bitmap::create(1, 1, vec![0xFF0000FF]));
+
+        // the header chunk follows the signature, its length & its type
+        data[16 .. 20].copy_from_slice(&width.to_be_bytes());
+        data[24] = depth;
+        data[25] = color_type;
+
+        data
+    };
+
+    for depth in [0, 3, 5, 6, 7, 16]
+    {
+        assert!(png::decode(&png_header(1, depth, 3)).is_err(), "palette images can't be {} bits deep", depth);
+    }
+
+    assert!(png::decode(&png_header(1, 4, 2)).is_err());
+    assert!(png::decode(&png_header(0, 8, 2)).is_err());
+
+    let mut bmp_header: Vec<u8> = vec![0; 54];
+
+    bmp_header[0 .. 2].copy_from_slice(b"BM");
+    bmp_header[18 .. 22].copy_from_slice(&(-2i32).to_le_bytes());
+    bmp_header[22 .. 26].copy_from_slice(&1i32.to_le_bytes());
+    bmp_header[28] = 24;
+
+    assert!(bmp::decode(&bmp_header).is_err());
+
+    assert!(ppm::decode(b"P6 4294967296 4294967296 255 ").is_err());
+    assert!(ppm::decode(b"P6 0 0 255 ").is_err());
+}
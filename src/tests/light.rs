@@ -0,0 +1,171 @@
+use crate::{algebra::{matrix3x3::*, matrix4x4::*, vector3::*, vector4::*}, topology::{color::*, material::*, vertex::*}, video::{light::*, shader::*, varyings::*, view::*}};
+
+use std::rc::Rc;
+
+fn white() -> Color
+{
+    Color::create(1.0, 1.0, 1.0)
+}
+
+#[test]
+fn directional()
+{
+    let light: Light = Light::directional(&Vector3::create(0.0, -2.0, 0.0), &white(), 1.0);
+
+    let (direction, strength) = light.incident(&Vector3::create(5.0, 5.0, 5.0));
+
+    assert!((direction.y - 1.0).abs() < 1e-6);
+    assert_eq!(strength, 1.0);
+}
+
+#[test]
+fn point_attenuation()
+{
+    let light: Light = Light::point(&Vector3::create(0.0, 2.0, 0.0), &white(), 1.0);
+
+    let (direction, near) = light.incident(&Vector3::create(0.0, 1.0, 0.0));
+    let (_, far) = light.incident(&Vector3::create(0.0, -8.0, 0.0));
+
+    assert!((direction.y - 1.0).abs() < 1e-6);
+    assert!((near - 1.0 / (1.0 + 0.09 + 0.032)).abs() < 1e-6);
+    assert!(far < near);
+}
+
+#[test]
+fn spot_cone()
+{
+    let light: Light = Light::spot(&Vector3::create(0.0, 1.0, 0.0), &Vector3::create(0.0, -1.0, 0.0), 10.0, 20.0, &white(), 1.0);
+
+    let (_, center) = light.incident(&Vector3::create(0.0, 0.0, 0.0));
+    // 15 degrees off the axis, halfway through the fade
+    let (_, edge) = light.incident(&Vector3::create(f32::tan(f32::to_radians(15.0)), 0.0, 0.0));
+    let (_, outside) = light.incident(&Vector3::create(1.0, 0.0, 0.0));
+
+    assert!(center > 0.0);
+    assert!(edge > 0.0 && edge < center);
+    assert_eq!(outside, 0.0);
+}
+
+#[test]
+fn lit_in_world_space()
+{
+    let uniforms: Uniforms = Uniforms
+    {
+        model: Matrix4x4::identity(),
+        normal_matrix: Matrix3x3::identity(),
+        view_projection: Matrix4x4::identity(),
+
+        view: View::create(&Vector3::create(0.0, 0.0, -3.0), &Vector3::zero()),
+        lights: vec![Light::spot(&Vector3::create(0.0, 1.0, 0.0), &Vector3::create(0.0, -1.0, 0.0), 10.0, 20.0, &white(), 1.0)],
+        ambient: Color::blank(),
+
+        material: Rc::new(Material::create()),
+    };
+
+    // right under the spot in world space, but far away in screen space
+    let mut fragment: Varyings = Varyings::create(Vector4::create(400.0, 300.0, 0.5, 1.0));
+
+    fragment.set_vector3(NORMAL, &Vector3::create(0.0, 1.0, 0.0));
+    fragment.set_color(COLOR, &white());
+    fragment.set_vector3(WORLD, &Vector3::zero());
+
+    let derivatives: Derivatives = Derivatives { dx: fragment, dy: fragment };
+
+    let lit: Color = Phong.fragment(&fragment, &derivatives, &uniforms).unwrap();
+
+    assert!(lit.r > 0.5);
+
+    // and the other way around
+    fragment.position = Vector4::create(0.0, 0.0, 0.5, 1.0);
+    fragment.set_vector3(WORLD, &Vector3::create(400.0, 300.0, 0.5));
+
+    let unlit: Color = Phong.fragment(&fragment, &derivatives, &uniforms).unwrap();
+
+    assert_eq!(unlit.r, 0.0);
+}
+
+#[test]
+fn blinn_phong_surface()
+{
+    let view: View = View::create(&Vector3::create(0.0, 0.0, -3.0), &Vector3::zero());
+    let lights: Vec<Light> = vec![Light::spot(&Vector3::create(0.0, 1.0, 0.0), &Vector3::create(0.0, -1.0, 0.0), 10.0, 20.0, &white(), 1.0)];
+
+    // right under the spot
+    let mut surface: Surface = Surface { position: Vector3::zero(), normal: Vector3::create(0.0, 1.0, 0.0), color: white(), specular: white() };
+
+    let lit: Color = blinn_phong(&surface, &view, &lights, &Color::blank(), &Material::create());
+
+    assert!(lit.r > 0.5);
+
+    // out of the cone
+    surface.position = Vector3::create(4.0, 0.0, 0.0);
+
+    let unlit: Color = blinn_phong(&surface, &view, &lights, &Color::blank(), &Material::create());
+
+    assert_eq!(unlit.r, 0.0);
+
+    // facing away from the light
+    surface.position = Vector3::zero();
+    surface.normal = Vector3::create(0.0, -1.0, 0.0);
+
+    assert_eq!(blinn_phong(&surface, &view, &lights, &Color::blank(), &Material::create()).r, 0.0);
+}
+
+// the point & spot lights are evaluated at the world position carried by the fragment, not at its place on the screen
+#[test]
+fn lit_at_the_world_position()
+{
+    let uniforms: Uniforms = Uniforms
+    {
+        model: Matrix4x4::translate(0.0, 0.0, 5.0),
+        normal_matrix: Matrix3x3::identity(),
+        view_projection: Matrix4x4::perspective(75.0, 1.0, 0.1, 100.0),
+
+        view: View::create(&Vector3::create(0.0, 0.0, -3.0), &Vector3::zero()),
+        lights: vec![Light::point(&Vector3::create(0.0, 1.0, 5.0), &white(), 1.0)],
+        ambient: Color::blank(),
+
+        material: Rc::new(Material::create()),
+    };
+
+    let mut vertex: Vertex = Vertex::partial(0.0, 0.0, 0.0, 0.0, 0.0, Vector3::create(0.0, 1.0, 0.0));
+
+    vertex.color = white();
+
+    let varyings: Varyings = Phong.vertex(&vertex, &uniforms);
+
+    // the vertex stage hands the model space position on in world space, whatever the projection does with it
+    assert_eq!(varyings.vector3(WORLD), Vector3::create(0.0, 0.0, 5.0));
+
+    let derivatives: Derivatives = Derivatives { dx: varyings, dy: varyings };
+
+    let under: Color = Phong.fragment(&varyings, &derivatives, &uniforms).unwrap();
+
+    // the same spot on the screen, far away from the light in the world
+    let mut away: Varyings = varyings;
+
+    away.set_vector3(WORLD, &Vector3::create(0.0, 0.0, 50.0));
+
+    let far: Color = Phong.fragment(&away, &derivatives, &uniforms).unwrap();
+
+    assert!(under.r > 0.5);
+    assert!(far.r < under.r * 0.1);
+}
+
+#[test]
+fn at_the_light_position()
+{
+    let position: Vector3 = Vector3::create(1.0, 2.0, 3.0);
+
+    for light in [Light::point(&position, &white(), 1.0), Light::spot(&position, &Vector3::create(0.0, -1.0, 0.0), 10.0, 20.0, &white(), 1.0)]
+    {
+        assert_eq!(light.incident(&position).1, 0.0);
+
+        let surface: Surface = Surface { position, normal: Vector3::create(0.0, 1.0, 0.0), color: white(), specular: white() };
+        let view: View = View::create(&Vector3::create(0.0, 0.0, -3.0), &Vector3::zero());
+
+        let color: Color = blinn_phong(&surface, &view, &[light], &Color::create(0.1, 0.1, 0.1), &Material::create());
+
+        assert!(color.r.is_finite() && color.g.is_finite() && color.b.is_finite());
+    }
+}
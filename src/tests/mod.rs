@@ -0,0 +1,8 @@
+mod algebra;
+mod golden;
+mod image;
+mod light;
+mod model;
+mod scenes;
+mod texture;
+mod varyings;
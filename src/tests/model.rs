@@ -0,0 +1,435 @@
+use crate::algebra::vector3::*;
+use crate::topology::{color::*, material::*, mesh::*, model::*, vertex::*};
+
+use std::{collections::HashMap, path::Path};
+
+fn asset(name: &str) -> String
+{
+    format!("{}/assets/golden/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn material_library()
+{
+    let materials: HashMap<String, Material> = Material::load_mtl(asset("cube_materials.mtl")).unwrap();
+
+    assert_eq!(materials.len(), 2);
+
+    let red: &Material = &materials["red"];
+
+    assert_eq!((red.diffuse.r, red.diffuse.g, red.diffuse.b), (0.9, 0.2, 0.2));
+    assert_eq!(red.specular.r, 0.3);
+    assert_eq!(red.emissive.r, 0.1);
+    assert_eq!(red.shininess, 10.0);
+    assert!(red.diffuse_map.is_none());
+
+    let checker: &Material = &materials["checker"];
+
+    assert_eq!(checker.opacity, 1.0);
+    assert_eq!(checker.diffuse_map.as_ref().unwrap().width(), 64);
+}
+
+#[test]
+fn material_errors()
+{
+    let message = |source: &str| Material::read_mtl(source, Path::new("")).err().map(|e| e.to_string()).unwrap_or_default();
+
+    assert!(message("newmtl glass\nKd 1 1 1\nd abc\n").starts_with("line 3: invalid number 'abc'"));
+    assert!(message("newmtl red\nKs\n").starts_with("line 2:"));
+    assert!(message("newmtl red\n\nmap_Kd missing.png\n").starts_with("line 3: failed to load texture"));
+
+    // a library that can't be loaded fails the model at its mtllib line
+    assert!(parse("v 0 0 0\nmtllib missing.mtl\n").err().map(|e| e.to_string()).unwrap_or_default().starts_with("line 2: failed to load material library"));
+}
+
+#[test]
+fn material_groups()
+{
+    let model: Model = Model::load_obj(asset("cube_materials.obj"), 1.0).unwrap();
+
+    let groups: Vec<(usize, usize)> = model.groups.iter().map(|g| (g.index, g.count)).collect();
+
+    assert_eq!(groups, vec![(0, 4), (12, 4), (24, 2), (30, 2)]);
+
+    // both checker groups share the same material, the unknown one falls back to gray
+    assert!(std::rc::Rc::ptr_eq(&model.groups[0].material, &model.groups[2].material));
+    assert_eq!(model.groups[3].material.diffuse.r, 0.5);
+}
+
+fn parse(source: &str) -> Result<Model, ObjError>
+{
+    Model::read_obj(source.as_bytes(), Path::new(""), 1.0)
+}
+
+const HEADER: &str = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvt 0 0\nvt 1 0\nvt 0 1\nvn 0 0 1\n";
+
+#[test]
+fn face_forms()
+{
+    for face in ["f 1 2 3", "f 1/1 2/2 3/3", "f 1//1 2//1 3//1", "f 1/1/1 2/2/1 3/3/1", "f -3/-3/-1 -2/-2/-1 -1/-1/-1"]
+    {
+        let model: Model = parse(&format!("{}{}\n", HEADER, face)).unwrap_or_else(|e| panic!("'{}' failed: {}", face, e));
+
+        assert_eq!(model.vertices.len(), 3, "{}", face);
+        assert_eq!(model.vertices[1].x, -1.0, "{}", face);
+        assert_eq!(model.vertices[2].y, 1.0, "{}", face);
+
+        // the normal is either read or calculated from the (counter-clockwise) face
+        assert_eq!(model.vertices[0].normal.z, 1.0, "{}", face);
+    }
+}
+
+#[test]
+fn continuations_and_comments()
+{
+    let model: Model = parse(&format!("# header\n{}f 1/1/1 \\\n  2/2/1 \\\n  3/3/1 # the last corner\n", HEADER)).unwrap();
+
+    assert_eq!(model.vertices.len(), 3);
+    assert_eq!(model.vertices[1].u, 1.0);
+}
+
+#[test]
+fn errors_have_line_numbers()
+{
+    let message = |source: &str| parse(source).err().map(|e| e.to_string()).unwrap_or_default();
+
+    assert!(message("v 0 0 0\nv 1 x 0\n").starts_with("line 2:"));
+    assert!(message(&format!("{}\nf 1 2 4\n", HEADER)).starts_with("line 9:"));
+    assert!(message(&format!("{}f 1 2 0\n", HEADER)).starts_with("line 8:"));
+    assert!(message(&format!("{}f 1 2\n", HEADER)).starts_with("line 8:"));
+    assert!(message(&format!("{}f 1/1/1/1 2 3\n", HEADER)).starts_with("line 8:"));
+
+    assert!(matches!(Model::load_obj(asset("missing.obj"), 1.0), Err(ObjError::IO(_))));
+}
+
+fn triangles(model: &Model) -> Vec<[Vertex; 3]>
+{
+    model.indices.chunks(3).map(|t| [model.vertices[t[0]], model.vertices[t[1]], model.vertices[t[2]]]).collect()
+}
+
+// twice the area of every triangle, signed by its winding as seen from +z (x is mirrored by the loader)
+fn triangle_areas(model: &Model) -> Vec<f32>
+{
+    triangles(model).iter().map(|t| -((t[1].x - t[0].x) * (t[2].y - t[0].y) - (t[1].y - t[0].y) * (t[2].x - t[0].x))).collect()
+}
+
+#[test]
+fn quads_and_ngons()
+{
+    let model: Model = parse("v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nv 2 0.5 0\nf 1 2 3 4\nf 2 5 3\nf 1 2 5 3 4\n").unwrap();
+
+    assert_eq!(model.indices.len(), (2 + 1 + 3) * 3);
+    assert_eq!(model.groups[0].count, 6);
+    assert!(triangle_areas(&model).iter().all(|a| *a > 0.0));
+}
+
+#[test]
+fn concave_polygon()
+{
+    // an L shape, with the concave corner at (1, 1)
+    let model: Model = parse("v 0 0 0\nv 2 0 0\nv 2 1 0\nv 1 1 0\nv 1 2 0\nv 0 2 0\nf 1 2 3 4 5 6\n").unwrap();
+
+    let areas: Vec<f32> = triangle_areas(&model);
+
+    assert_eq!(areas.len(), 4);
+    assert!(areas.iter().all(|a| *a > 0.0));
+    assert!((areas.iter().sum::<f32>() - 6.0).abs() < 1e-5);
+}
+
+#[test]
+fn concave_polygon_facing_away()
+{
+    // an L shape lying in the xz plane, facing down
+    let model: Model = parse("v 0 0 0\nv 0 0 2\nv 1 0 2\nv 1 0 1\nv 2 0 1\nv 2 0 0\nf 6 5 4 3 2 1\n").unwrap();
+
+    assert_eq!(model.indices.len(), 12);
+
+    // every triangle keeps the normal of the polygon (-y)
+    for t in triangles(&model)
+    {
+        assert_eq!(t[0].normal.y, -1.0);
+    }
+}
+
+#[test]
+fn shared_vertices_are_welded()
+{
+    let model: Model = Model::load_obj(asset("cube.obj"), 1.0).unwrap();
+
+    // 8 corners, each used by 3 faces with a different uv & normal
+    assert_eq!(model.vertices.len(), 24);
+    assert_eq!(model.indices.len(), 36);
+
+    let mesh: Mesh = Mesh::converted(&model);
+
+    assert_eq!(mesh.vertices.len(), 24);
+    assert_eq!(mesh.indices, model.indices);
+}
+
+#[test]
+fn epsilon_weld()
+{
+    // two triangles of a quad whose shared corners are slightly apart
+    let model: Model = parse("v 0 0 0\nv 1 0 0\nv 1 1 0\nv 1.00001 1 0\nv 0 1 0\nv 0 0.00001 0\nf 1 2 3\nf 4 5 6\n").unwrap();
+
+    let mut mesh: Mesh = Mesh::converted(&model);
+
+    assert_eq!(mesh.vertices.len(), 6);
+
+    mesh.weld(0.001);
+
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.indices, vec![0, 1, 2, 2, 3, 0]);
+
+    // nothing is close enough
+    mesh.weld(0.0);
+
+    assert_eq!(mesh.vertices.len(), 4);
+}
+
+#[test]
+fn weld_a_sphere()
+{
+    let mut mesh: Mesh = Mesh::sphere(8, 4, 1.0);
+
+    let triangles: usize = mesh.indices.len() / 3;
+
+    mesh.weld(1e-4);
+
+    assert!(mesh.indices.iter().all(|&i| i < mesh.vertices.len()));
+    assert_eq!(mesh.indices.len() / 3, triangles);
+
+    // the uvs keep the seam & pole copies apart, so there is nothing to merge
+    assert_eq!(mesh.vertices.len(), 9 * 5);
+}
+
+// an axis aligned cube centered on the origin, without normals
+const CUBE: &str = "v -1 -1 -1\nv 1 -1 -1\nv 1 1 -1\nv -1 1 -1\nv -1 -1 1\nv 1 -1 1\nv 1 1 1\nv -1 1 1\n\
+f 1 4 3 2\nf 5 6 7 8\nf 1 2 6 5\nf 4 8 7 3\nf 1 5 8 4\nf 2 3 7 6\n";
+
+#[test]
+fn generated_normals_keep_hard_edges()
+{
+    let model: Model = parse(CUBE).unwrap();
+
+    // the faces meet at right angles, so every corner is split in 3
+    assert_eq!(model.vertices.len(), 24);
+
+    for t in triangles(&model)
+    {
+        let a: Vector3 = Vector3::create(t[0].x, t[0].y, t[0].z);
+        let b: Vector3 = Vector3::create(t[1].x, t[1].y, t[1].z);
+        let c: Vector3 = Vector3::create(t[2].x, t[2].y, t[2].z);
+
+        let center: Vector3 = (a + b + c) / 3.0;
+
+        for v in &t
+        {
+            // flat & pointing outwards
+            assert_eq!(v.normal.x.abs() + v.normal.y.abs() + v.normal.z.abs(), 1.0);
+            assert!(Vector3::dot(&v.normal, &center) > 0.0);
+        }
+    }
+}
+
+#[test]
+fn smooth_normals_across_edges()
+{
+    let mut mesh: Mesh = Mesh::converted(&parse(CUBE).unwrap());
+
+    // angles, since how the quads were split into triangles must not matter
+    mesh.smooth_normals(180.0, Weighting::ANGLE, true);
+
+    assert_eq!(mesh.vertices.len(), 24);
+
+    // every corner points away from the center
+    for v in &mesh.vertices
+    {
+        let expected: Vector3 = Vector3::create(v.x, v.y, v.z).normalized();
+
+        assert!((v.normal - expected).length() < 1e-5);
+    }
+}
+
+#[test]
+fn smooth_normals_on_a_sphere()
+{
+    let mut mesh: Mesh = Mesh::sphere(8, 4, 1.0);
+
+    mesh.smooth_normals(180.0, Weighting::ANGLE, true);
+
+    assert!(mesh.indices.iter().all(|&i| i < mesh.vertices.len()));
+
+    // close to the positions of a unit sphere, only as close as its few faces allow, the seam & poles included
+    for v in &mesh.vertices
+    {
+        assert!(Vector3::dot(&v.normal, &Vector3::create(v.x, v.y, v.z)) > 0.99);
+    }
+}
+
+#[test]
+fn normal_weighting()
+{
+    // three faces around the origin, the one facing +z being half the size of the others
+    let source: &str = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 0 0 1\nv 1 1 0\nv 0 1 1\nv 1 0 1\nf 1 2 3\nf 1 3 6 4\nf 1 4 7 2\n";
+
+    let corner = |weighting: Weighting| -> Vector3
+    {
+        let mut mesh: Mesh = Mesh::converted(&parse(source).unwrap());
+
+        mesh.smooth_normals(180.0, weighting, true);
+
+        let normals: Vec<Vector3> = mesh.vertices.iter().filter(|v| v.x == 0.0 && v.y == 0.0 && v.z == 0.0).map(|v| v.normal).collect();
+
+        assert!(normals.iter().all(|n| (*n - normals[0]).length() < 1e-6));
+
+        normals[0]
+    };
+
+    // the corner of each face is a right angle, so they count the same (note the mirrored x)
+    assert!((corner(Weighting::ANGLE) - Vector3::create(-1.0, 1.0, 1.0).normalized()).length() < 1e-5);
+
+    // the quads are made of two triangles touching the corner, each as large as the +z face
+    assert!((corner(Weighting::AREA) - Vector3::create(-2.0, 2.0, 1.0).normalized()).length() < 1e-5);
+}
+
+#[test]
+fn tangent_space()
+{
+    let mut mesh: Mesh = Mesh::plane(&Color::create(1.0, 1.0, 1.0));
+
+    // u runs along x & v along z
+    for v in &mesh.vertices
+    {
+        assert!((v.tangent - Vector3::create(1.0, 0.0, 0.0)).length() < 1e-5);
+        assert!((v.bitangent - Vector3::create(0.0, 0.0, 1.0)).length() < 1e-5);
+        assert!(Vector3::dot(&v.tangent, &v.normal).abs() < 1e-5);
+    }
+
+    // mirrored uvs flip the tangent, the bitangent stays
+    for v in &mut mesh.vertices
+    {
+        v.u = 1.0 - v.u;
+    }
+
+    mesh.calculate_tangents();
+
+    for v in &mesh.vertices
+    {
+        assert!((v.tangent - Vector3::create(-1.0, 0.0, 0.0)).length() < 1e-5);
+        assert!((v.bitangent - Vector3::create(0.0, 0.0, 1.0)).length() < 1e-5);
+    }
+}
+
+#[test]
+fn obj_tangents_follow_the_mirrored_x()
+{
+    let model: Model = parse("v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nvt 0 0\nvt 1 0\nvt 1 1\nvt 0 1\nvn 0 0 1\nf 1/1/1 2/2/1 3/3/1 4/4/1\n").unwrap();
+
+    let mesh: Mesh = Mesh::converted(&model);
+
+    for v in &mesh.vertices
+    {
+        assert!((v.tangent - Vector3::create(-1.0, 0.0, 0.0)).length() < 1e-5);
+        assert!((v.bitangent - Vector3::create(0.0, 1.0, 0.0)).length() < 1e-5);
+    }
+}
+
+#[test]
+fn normal_matrix()
+{
+    let mut mesh: Mesh = Mesh::plane(&Color::create(1.0, 1.0, 1.0));
+
+    // a 45 degree slope, facing up & towards +x
+    mesh.vertices[0].normal = Vector3::create(1.0, 1.0, 0.0).normalized();
+
+    // stretched along x, the slope gets flatter & the normal turns upwards
+    mesh.scale = Vector3::create(2.0, 1.0, 1.0);
+    mesh.update();
+
+    assert_eq!(mesh.vertices[0].transform_normal(&mesh.normal_matrix), Vector3::create(0.5, 1.0, 0.0).normalized());
+
+    // the tangent follows the surface & stays perpendicular to the normal
+    mesh.vertices[0].tangent = Vector3::create(1.0, -1.0, 0.0).normalized();
+
+    let (tangent, _) = mesh.vertices[0].transform_tangents(&mesh.model.upper());
+
+    assert_eq!(tangent, Vector3::create(2.0, -1.0, 0.0).normalized());
+    assert!(Vector3::dot(&tangent, &mesh.vertices[0].transform_normal(&mesh.normal_matrix)).abs() < 1e-6);
+
+    // uniform scale & rotation keep the normal unit length
+    mesh.scale = Vector3::create(3.0, 3.0, 3.0);
+    mesh.rotation.y = 90.0;
+    mesh.update();
+
+    assert_eq!(mesh.vertices[0].transform_normal(&mesh.normal_matrix), Vector3::create(0.0, 1.0, -1.0).normalized());
+}
+
+// the same non-uniform stretch, in millimeters
+#[test]
+fn small_scale_normal_matrix()
+{
+    let mut mesh: Mesh = Mesh::plane(&Color::create(1.0, 1.0, 1.0));
+
+    mesh.vertices[0].normal = Vector3::create(1.0, 1.0, 0.0).normalized();
+
+    mesh.scale = Vector3::create(0.004, 0.002, 0.002);
+    mesh.update();
+
+    assert_eq!(mesh.vertices[0].transform_normal(&mesh.normal_matrix), Vector3::create(0.5, 1.0, 0.0).normalized());
+}
+
+// OBJ files are right-handed & the engine is left-handed, so the loader negates x, which must apply to the normals too
+#[test]
+fn obj_normals_change_hands_with_the_positions()
+{
+    // a face that points towards +x & +y in the file, with its normal given
+    let model: Model = parse("v 1 0 0\nv 0 1 0\nv 1 0 1\nvn 1 1 0\nf 1//1 2//1 3//1\n").unwrap();
+
+    // the face now lies on the -x side, so does its normal
+    let expected: Vector3 = Vector3::create(-1.0, 1.0, 0.0);
+
+    assert!(model.vertices.iter().all(|v| v.x <= 0.0 && v.normal == expected));
+
+    // & it agrees with the normal generated for the same face without one
+    let generated: Model = parse("v 1 0 0\nv 0 1 0\nv 1 0 1\nf 1 2 3\n").unwrap();
+
+    assert!(generated.vertices.iter().all(|v| v.normal == expected.normalized()));
+}
+
+// two quads meeting at x = 0 with the uvs of the left one mirrored, the seam vertices are shared in the file
+#[test]
+fn mirrored_uv_seams_split_the_tangents()
+{
+    let source: &str = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nv -1 0 0\nv -1 1 0\n\
+                        vt 0 0\nvt 1 0\nvt 1 1\nvt 0 1\nvn 0 0 1\n\
+                        f 1/1/1 2/2/1 3/3/1 4/4/1\nf 5/2/1 1/1/1 4/4/1 6/3/1\n";
+
+    let model: Model = parse(source).unwrap();
+    let mesh: Mesh = Mesh::converted(&model);
+
+    assert_eq!(model.vertices.len(), 6);
+    assert_eq!(mesh.vertices.len(), 8);
+
+    // each side follows its own uvs instead of averaging to nothing on the seam
+    for v in &mesh.vertices
+    {
+        assert!((v.tangent.length() - 1.0).abs() < 1e-5);
+        assert!((v.bitangent - Vector3::create(0.0, 1.0, 0.0)).length() < 1e-5);
+    }
+}
+
+#[test]
+fn tangents_follow_the_topology_changes()
+{
+    let mut mesh: Mesh = Mesh::plane(&Color::create(1.0, 1.0, 1.0));
+
+    for v in &mut mesh.vertices
+    {
+        v.tangent = Vector3::zero();
+    }
+
+    mesh.weld(0.0);
+
+    assert!(mesh.vertices.iter().all(|v| (v.tangent.length() - 1.0).abs() < 1e-5));
+}
@@ -0,0 +1,552 @@
+use super::golden;
+use crate::{algebra::{quaternion::*, vector3::*, vector4::*}, image::bitmap::*, topology::{color::*, material::*, mesh::*, model::*, part::*, texture::*, vertex::*}, video::{framebuffer::*, light::*, render::*, shader::*, target::*, varyings::*, view::*}};
+
+use std::rc::Rc;
+
+const WIDTH: i32 = 160;
+const HEIGHT: i32 = 120;
+
+fn render(mesh: &mut Mesh, view: View) -> Bitmap
+{
+    render_lit(mesh, view, None)
+}
+
+// replaces the default lighting when lights are given
+fn render_lit(mesh: &mut Mesh, view: View, lights: Option<Vec<Light>>) -> Bitmap
+{
+    let mut render = Render::create(Framebuffer::create(WIDTH, HEIGHT), view);
+
+    if let Some(lights) = lights
+    {
+        render.lights = lights;
+    }
+
+    render.clear(Color::unpacked(0x181818FF));
+    render.process(mesh);
+
+    render.target.capture()
+}
+
+fn front_view() -> View
+{
+    View::create(&Vector3::create(0.0, 0.0, -3.0), &Vector3::zero())
+}
+
+// looks down on the scene, so the top faces are visible as well
+fn elevated_view() -> View
+{
+    View::create(&Vector3::create(0.0, 1.5, -2.5), &Vector3::create(-30.0, 0.0, 0.0))
+}
+
+#[test]
+fn triangle()
+{
+    let mut mesh: Mesh = Mesh::triangle();
+
+    mesh.scale = Vector3::create(2.0, 2.0, 2.0);
+
+    golden::compare("triangle", &render(&mut mesh, front_view()));
+}
+
+#[test]
+fn cube()
+{
+    let mut mesh: Mesh = Mesh::cube();
+
+    mesh.rotation.y = 30.0;
+
+    golden::compare("cube", &render(&mut mesh, elevated_view()));
+}
+
+#[test]
+fn sphere()
+{
+    let mut mesh: Mesh = Mesh::sphere(40, 20, 1.0);
+
+    golden::compare("sphere", &render(&mut mesh, front_view()));
+}
+
+#[test]
+fn squashed_sphere()
+{
+    let mut mesh: Mesh = Mesh::sphere(40, 20, 1.0);
+
+    mesh.scale = Vector3::create(1.5, 0.5, 1.0);
+    mesh.rotation.z = 30.0;
+
+    golden::compare("squashed_sphere", &render(&mut mesh, front_view()));
+}
+
+#[test]
+fn obj()
+{
+    let model: Model = Model::load_obj(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/golden/cube.obj"), 1.0).unwrap();
+
+    let mut mesh: Mesh = Mesh::converted(&model);
+
+    mesh.rotation.y = -30.0;
+
+    golden::compare("obj", &render(&mut mesh, elevated_view()));
+}
+
+#[test]
+fn obj_materials()
+{
+    let model: Model = Model::load_obj(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/golden/cube_materials.obj"), 1.0).unwrap();
+
+    let mut mesh: Mesh = Mesh::converted(&model);
+
+    mesh.rotation.y = -30.0;
+
+    golden::compare("obj_materials", &render(&mut mesh, elevated_view()));
+}
+
+// a warm key light from the top left & a cold rim light from behind
+#[test]
+fn sphere_lights()
+{
+    let mut mesh: Mesh = Mesh::sphere(40, 20, 1.0);
+
+    let lights: Vec<Light> = vec!
+    [
+        Light::directional(&Vector3::create(1.0, -1.0, 1.0), &Color::create(1.0, 0.8, 0.6), 0.9),
+        Light::directional(&Vector3::create(-0.5, 0.2, -1.0), &Color::create(0.3, 0.5, 1.0), 0.6),
+    ];
+
+    golden::compare("sphere_lights", &render_lit(&mut mesh, front_view(), Some(lights)));
+}
+
+// one mesh, split into differently shaded parts
+#[test]
+fn materials()
+{
+    let mut mesh: Mesh = Mesh::cube();
+
+    for vertex in &mut mesh.vertices
+    {
+        vertex.color = Color::create(1.0, 1.0, 1.0);
+    }
+
+    let glossy: Material = Material { diffuse: Color::create(0.2, 0.9, 0.3), shininess: 8.0, ..Material::create() };
+    let matte: Material = Material { diffuse: Color::create(0.3, 0.3, 1.0), specular: Color::blank(), emissive: Color::create(0.2, 0.0, 0.0), ..Material::create() };
+
+    // top & bottom, then the four sides
+    mesh.parts = vec![Part::create(Topology::TRIANGLE, 0, 4), Part::create(Topology::TRIANGLE, 12, 8)];
+    mesh.parts[0].material = Rc::new(glossy);
+    mesh.parts[1].material = Rc::new(matte);
+
+    mesh.rotation.y = 30.0;
+
+    golden::compare("materials", &render(&mut mesh, elevated_view()));
+}
+
+// a translucent pane hovering over an opaque cube
+#[test]
+fn translucency()
+{
+    let mut render = Render::create(Framebuffer::create(WIDTH, HEIGHT), elevated_view());
+
+    let mut cube: Mesh = Mesh::cube();
+    let mut pane: Mesh = Mesh::plane(&Color::create(1.0, 1.0, 1.0));
+
+    cube.rotation.y = 30.0;
+
+    pane.position = Vector3::create(0.3, 0.7, -0.3);
+    pane.scale = Vector3::create(0.8, 1.0, 0.8);
+    pane.parts[0].material = Rc::new(Material { diffuse: Color::create(0.2, 0.6, 1.0), opacity: 0.5, ..Material::create() });
+
+    render.clear(Color::unpacked(0x181818FF));
+    render.process(&mut cube);
+    render.process(&mut pane);
+
+    golden::compare("translucency", &render.target.capture());
+}
+
+// the floor reaches behind the camera, so its triangles have to be clipped against the near plane
+#[test]
+fn floor()
+{
+    let mut mesh: Mesh = Mesh::plane(&Color::create(0.3, 0.6, 0.3));
+
+    mesh.position.y = -0.5;
+    mesh.scale = Vector3::create(20.0, 1.0, 20.0);
+
+    golden::compare("floor", &render(&mut mesh, floor_view()));
+}
+
+fn checker() -> Texture
+{
+    Texture::load(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/golden/checker.png")).unwrap()
+}
+
+#[test]
+fn textured_cube()
+{
+    let mut mesh: Mesh = Mesh::cube();
+
+    let mut texture: Texture = checker();
+
+    texture.filter = Filter::NEAREST;
+
+    mesh.rotation.y = 30.0;
+    mesh.parts[0].material = Rc::new(Material::textured(Rc::new(texture)));
+
+    golden::compare("textured_cube", &render(&mut mesh, elevated_view()));
+}
+
+fn textured_floor(texture: Texture) -> Mesh
+{
+    let mut mesh: Mesh = Mesh::plane(&Color::create(1.0, 1.0, 1.0));
+
+    // repeat the texture many times over the floor
+    for vertex in &mut mesh.vertices
+    {
+        vertex.u *= 10.0;
+        vertex.v *= 10.0;
+    }
+
+    mesh.position.y = -0.5;
+    mesh.scale = Vector3::create(20.0, 1.0, 20.0);
+    mesh.parts[0].material = Rc::new(Material::textured(Rc::new(texture)));
+
+    mesh
+}
+
+fn floor_view() -> View
+{
+    View::create(&Vector3::create(0.0, 0.0, -1.0), &Vector3::create(-10.0, 20.0, 0.0))
+}
+
+#[test]
+fn textured_floor_bilinear()
+{
+    let mut texture: Texture = checker();
+
+    texture.filter = Filter::BILINEAR;
+
+    golden::compare("textured_floor_bilinear", &render(&mut textured_floor(texture), floor_view()));
+}
+
+#[test]
+fn textured_floor_trilinear()
+{
+    golden::compare("textured_floor_trilinear", &render(&mut textured_floor(checker()), floor_view()));
+}
+
+#[test]
+fn textured_floor_anisotropic()
+{
+    let mut texture: Texture = checker();
+
+    texture.anisotropy = 8;
+
+    golden::compare("textured_floor_anisotropic", &render(&mut textured_floor(texture), floor_view()));
+}
+
+// a grid of 4x4 domes, the normals are stored in tangent space
+fn bumps() -> Texture
+{
+    let size: i32 = 64;
+
+    let pixels: Vec<u32> = (0 .. size * size).map(|i|
+    {
+        // position within the dome, from -1 to 1
+        let x: f32 = ((i % size) % 16) as f32 / 8.0 - 1.0 + 1.0 / 16.0;
+        let y: f32 = 1.0 - ((i / size) % 16) as f32 / 8.0 - 1.0 / 16.0;
+
+        let normal: Vector3 = if x * x + y * y < 0.8
+        {
+            Vector3::create(x, y, f32::sqrt(1.0 - x * x - y * y))
+        }
+        else
+        {
+            Vector3::create(0.0, 0.0, 1.0)
+        };
+
+        Color::create(normal.x * 0.5 + 0.5, normal.y * 0.5 + 0.5, normal.z * 0.5 + 0.5).packed()
+    }).collect();
+
+    Texture::create(&Bitmap::create(size, size, pixels))
+}
+
+#[test]
+fn normal_mapped_cube()
+{
+    let mut mesh: Mesh = Mesh::cube();
+
+    mesh.rotation.y = 30.0;
+    mesh.parts[0].material = Rc::new(Material { normal_map: Some(Rc::new(bumps())), ..Material::create() });
+
+    golden::compare("normal_mapped_cube", &render(&mut mesh, elevated_view()));
+}
+
+#[test]
+fn tilted_cube()
+{
+    let mut mesh: Mesh = Mesh::cube();
+
+    mesh.rotation = Vector3::create(25.0, 30.0, 15.0);
+
+    // a quarter turn around z on top of the euler angles
+    mesh.orientation = Quaternion::axis_angle(&Vector3::create(0.0, 0.0, 1.0), 90.0);
+
+    golden::compare("tilted_cube", &render(&mut mesh, front_view()));
+}
+
+// a point light close to the sphere, the falloff only shows up with positions in world space
+#[test]
+fn sphere_point_light()
+{
+    let mut mesh: Mesh = Mesh::sphere(40, 20, 1.0);
+
+    let lights: Vec<Light> = vec![Light::point(&Vector3::create(1.2, 1.0, -1.5), &Color::create(1.0, 0.9, 0.7), 1.5)];
+
+    golden::compare("sphere_point_light", &render_lit(&mut mesh, front_view(), Some(lights)));
+}
+
+// a spot light pointing down at the floor in front of the camera
+#[test]
+fn floor_spot_light()
+{
+    let mut mesh: Mesh = Mesh::plane(&Color::create(0.8, 0.8, 0.8));
+
+    mesh.position.y = -0.5;
+    mesh.scale = Vector3::create(20.0, 1.0, 20.0);
+
+    let lights: Vec<Light> = vec![Light::spot(&Vector3::create(0.5, 1.0, 2.0), &Vector3::create(0.0, -1.0, 0.0), 20.0, 30.0, &Color::create(1.0, 1.0, 1.0), 1.0)];
+
+    golden::compare("floor_spot_light", &render_lit(&mut mesh, floor_view(), Some(lights)));
+}
+
+// puffs the mesh up along its normals & cuts it into slices
+struct Sliced;
+
+impl Shader for Sliced
+{
+    fn vertex(&self, vertex: &Vertex, uniforms: &Uniforms) -> Varyings
+    {
+        let mut inflated: Vertex = *vertex;
+
+        inflated.x += vertex.normal.x * 0.2;
+        inflated.y += vertex.normal.y * 0.2;
+        inflated.z += vertex.normal.z * 0.2;
+
+        transform(&inflated, uniforms)
+    }
+
+    fn fragment(&self, fragment: &Varyings, derivatives: &Derivatives, uniforms: &Uniforms) -> Option<Color>
+    {
+        if (fragment.vector3(WORLD).y * 8.0).floor() as i32 % 2 == 0 { return None; }
+
+        Phong.fragment(fragment, derivatives, uniforms)
+    }
+}
+
+// a row of spheres, each with another shader
+#[test]
+fn shaders()
+{
+    let mut render = Render::create(Framebuffer::create(WIDTH, HEIGHT), View::create(&Vector3::create(0.0, 0.0, -5.0), &Vector3::zero()));
+
+    let shaders: [Rc<dyn Shader>; 4] = [Rc::new(Unlit), Rc::new(Toon { bands: 3 }), Rc::new(Normals), Rc::new(Sliced)];
+    let normals: usize = render.add_shader(Rc::new(Normals));
+
+    render.clear(Color::unpacked(0x181818FF));
+
+    for (i, shader) in shaders.into_iter().enumerate()
+    {
+        let mut mesh: Mesh = Mesh::sphere(24, 12, 0.6);
+
+        mesh.position.x = i as f32 * 1.6 - 2.4;
+
+        // the material's shader wins over the mesh's
+        mesh.shader = Some(normals);
+        mesh.parts[0].material = Rc::new(Material { shader: Some(render.add_shader(shader)), ..Material::create() });
+
+        render.process(&mut mesh);
+    }
+
+    golden::compare("shaders", &render.target.capture());
+}
+
+// cuts the mesh into slices along its own height, passed down in a custom varying, so they tilt with it
+struct Banded;
+
+impl Shader for Banded
+{
+    fn vertex(&self, vertex: &Vertex, uniforms: &Uniforms) -> Varyings
+    {
+        let mut varyings: Varyings = transform(vertex, uniforms);
+
+        varyings.set(CUSTOM, vertex.y);
+
+        varyings
+    }
+
+    fn fragment(&self, fragment: &Varyings, derivatives: &Derivatives, uniforms: &Uniforms) -> Option<Color>
+    {
+        if (fragment.get(CUSTOM) * 8.0).floor() as i32 % 2 == 0 { return None; }
+
+        Phong.fragment(fragment, derivatives, uniforms)
+    }
+}
+
+#[test]
+fn custom_varyings()
+{
+    let mut render = Render::create(Framebuffer::create(WIDTH, HEIGHT), View::create(&Vector3::create(0.0, 0.0, -5.0), &Vector3::zero()));
+
+    let banded: usize = render.add_shader(Rc::new(Banded));
+
+    render.clear(Color::unpacked(0x181818FF));
+
+    let mut mesh: Mesh = Mesh::sphere(24, 12, 1.2);
+
+    mesh.rotation.z = 30.0;
+    mesh.shader = Some(banded);
+
+    render.process(&mut mesh);
+
+    golden::compare("custom_varyings", &render.target.capture());
+}
+
+#[test]
+#[should_panic(expected = "unknown shader id 1")]
+fn unknown_shader()
+{
+    let mut render = Render::create(Framebuffer::create(WIDTH, HEIGHT), View::create(&Vector3::create(0.0, 0.0, -5.0), &Vector3::zero()));
+
+    // only the id 0 has been handed out
+    render.add_shader(Rc::new(Normals));
+
+    let mut mesh: Mesh = Mesh::cube();
+
+    mesh.shader = Some(1);
+
+    render.process(&mut mesh);
+}
+
+// an isometric-like view, the parallel edges of the cube stay parallel
+#[test]
+fn orthographic_cube()
+{
+    let mut mesh: Mesh = Mesh::cube();
+
+    mesh.rotation.y = 45.0;
+
+    let mut view: View = elevated_view();
+
+    view.set_projection(Projection::ORTHOGRAPHIC { height: 2.5 });
+
+    golden::compare("orthographic_cube", &render(&mut mesh, view));
+}
+
+// a narrow field of view shifted to the top right, the sphere ends up in the bottom left corner without turning the view
+#[test]
+fn off_axis_sphere()
+{
+    let mut mesh: Mesh = Mesh::sphere(40, 20, 1.0);
+
+    let mut view: View = front_view();
+
+    view.set_fov(50.0);
+    view.set_clip(1.0, 10.0);
+    view.set_shift(0.3, 0.25);
+
+    golden::compare("off_axis_sphere", &render(&mut mesh, view));
+}
+
+// the buffers follow the new size & the view the new aspect ratio, so the sphere stays round in a narrow target
+#[test]
+fn resized_target()
+{
+    let mut render = Render::create(Framebuffer::create(WIDTH, HEIGHT), front_view());
+
+    render.target.resize(WIDTH / 2, HEIGHT);
+
+    render.clear(Color::unpacked(0x181818FF));
+    render.process(&mut Mesh::sphere(40, 20, 1.0));
+
+    assert_eq!(render.target.depth_buffer().len(), (WIDTH / 2 * HEIGHT) as usize);
+    assert_eq!(render.view.aspect(), (WIDTH / 2) as f32 / HEIGHT as f32);
+
+    golden::compare("resized_target", &render.target.capture());
+}
+
+#[test]
+fn aspect_ratio_set_by_hand()
+{
+    let mut render = Render::create(Framebuffer::create(WIDTH, HEIGHT), front_view());
+
+    // stretched on purpose, drawing doesn't undo it
+    render.view.set_aspect(2.0);
+    render.process(&mut Mesh::cube());
+
+    assert_eq!(render.view.aspect(), 2.0);
+
+    // a resize does
+    render.target.resize(WIDTH, WIDTH);
+    render.process(&mut Mesh::cube());
+
+    assert_eq!(render.view.aspect(), 1.0);
+
+    // & an empty target leaves it alone
+    render.target.resize(WIDTH, 0);
+    render.process(&mut Mesh::cube());
+
+    assert_eq!(render.view.aspect(), 1.0);
+    // a new view is matched to the target it replaces the old one on, without waiting for a resize
+    render.target.resize(WIDTH, HEIGHT);
+    render.process(&mut Mesh::cube());
+
+    render.view = View::create(&Vector3::create(0.0, 0.0, -5.0), &Vector3::zero());
+    render.process(&mut Mesh::cube());
+
+    assert_eq!(render.view.aspect(), WIDTH as f32 / HEIGHT as f32);
+}
+
+// a vertex format of its own: a position & a temperature, from cold blue to hot red
+struct Heat
+{
+    position: Vector3,
+    temperature: f32,
+}
+
+struct Thermal;
+
+impl Shader<Heat> for Thermal
+{
+    fn vertex(&self, vertex: &Heat, uniforms: &Uniforms) -> Varyings
+    {
+        let mut varyings: Varyings = Varyings::create(uniforms.view_projection * uniforms.model * Vector4::extended(&vertex.position, 1.0));
+
+        varyings.set(CUSTOM, vertex.temperature);
+
+        varyings
+    }
+
+    fn fragment(&self, fragment: &Varyings, _: &Derivatives, _: &Uniforms) -> Option<Color>
+    {
+        let temperature: f32 = fragment.get(CUSTOM);
+
+        Some(Color::create(temperature, 0.0, 1.0 - temperature))
+    }
+}
+
+#[test]
+fn custom_vertex_format()
+{
+    let mut render = Render::create(Framebuffer::create(WIDTH, HEIGHT), front_view());
+
+    let corners: [(f32, f32, f32); 4] = [(-1.0, -1.0, 0.0), (1.0, -1.0, 0.0), (1.0, 1.0, 1.0), (-1.0, 1.0, 1.0)];
+    let vertices: Vec<Heat> = corners.iter().map(|(x, y, temperature)| Heat { position: Vector3::create(*x, *y, 0.0), temperature: *temperature }).collect();
+
+    let mut mesh: Mesh<Heat> = Mesh::create(vertices, vec![0, 1, 2, 2, 3, 0], vec![Part::create(Topology::TRIANGLE, 0, 2)]);
+
+    mesh.rotation.z = 30.0;
+
+    render.clear(Color::unpacked(0x181818FF));
+    render.process_with(&mut mesh, Rc::new(Thermal));
+
+    golden::compare("custom_vertex_format", &render.target.capture());
+}
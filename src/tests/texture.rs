@@ -0,0 +1,90 @@
+use crate::{image::bitmap::*, topology::{color::*, texture::*}};
+
+// 2x1 texture, black on the left & white on the right
+fn texture(filter: Filter, wrap: Wrap) -> Texture
+{
+    let mut texture: Texture = Texture::create(&Bitmap::create(2, 1, vec![0x000000FF, 0xFFFFFFFF]));
+
+    texture.filter = filter;
+    texture.wrap = wrap;
+
+    texture
+}
+
+fn brightness(color: Color) -> f32
+{
+    (color.r + color.g + color.b) / 3.0
+}
+
+#[test]
+fn nearest()
+{
+    let texture: Texture = texture(Filter::NEAREST, Wrap::REPEAT);
+
+    assert_eq!(brightness(texture.sample(0.25, 0.5)), 0.0);
+    assert_eq!(brightness(texture.sample(0.75, 0.5)), 1.0);
+    assert_eq!(brightness(texture.sample(1.25, 0.5)), 0.0);
+}
+
+#[test]
+fn bilinear()
+{
+    let texture: Texture = texture(Filter::BILINEAR, Wrap::CLAMP);
+
+    // halfway between the two texel centers
+    assert!((brightness(texture.sample(0.5, 0.5)) - 0.5).abs() < 1e-6);
+    assert_eq!(brightness(texture.sample(0.0, 0.5)), 0.0);
+    assert_eq!(brightness(texture.sample(1.0, 0.5)), 1.0);
+}
+
+#[test]
+fn wrap_modes()
+{
+    assert_eq!(brightness(texture(Filter::NEAREST, Wrap::REPEAT).sample(-0.25, 0.5)), 1.0);
+    assert_eq!(brightness(texture(Filter::NEAREST, Wrap::CLAMP).sample(-0.25, 0.5)), 0.0);
+    assert_eq!(brightness(texture(Filter::NEAREST, Wrap::CLAMP).sample(3.75, 0.5)), 1.0);
+    assert_eq!(brightness(texture(Filter::NEAREST, Wrap::MIRROR).sample(1.25, 0.5)), 1.0);
+    assert_eq!(brightness(texture(Filter::NEAREST, Wrap::MIRROR).sample(1.75, 0.5)), 0.0);
+}
+
+#[test]
+fn mip_chain()
+{
+    let odd: Texture = Texture::create(&Bitmap::create(5, 3, vec![0x808080FF; 15]));
+
+    let sizes: Vec<(i32, i32)> = odd.levels.iter().map(|l| (l.width, l.height)).collect();
+
+    assert_eq!(sizes, vec![(5, 3), (2, 1), (1, 1)]);
+
+    // the smallest level of a 2x1 black & white texture is their average
+    let texture: Texture = texture(Filter::TRILINEAR, Wrap::REPEAT);
+
+    assert!((brightness(texture.levels[1].texels[0]) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn minification_picks_smaller_levels()
+{
+    let texture: Texture = texture(Filter::TRILINEAR, Wrap::REPEAT);
+
+    // one pixel covers the whole texture, so only the 1x1 average is left
+    assert!((brightness(texture.sample_gradient(0.25, 0.5, (1.0, 0.0), (0.0, 1.0))) - 0.5).abs() < 1e-6);
+    // magnified, the full size image is used
+    assert_eq!(brightness(texture.sample_gradient(0.0, 0.5, (0.01, 0.0), (0.0, 0.01))), 0.5);
+    assert!(brightness(texture.sample_gradient(0.3, 0.5, (0.01, 0.0), (0.0, 0.01))) < 0.5);
+}
+
+#[test]
+fn degenerate_footprint_uses_the_full_size_image()
+{
+    let texture: Texture = texture(Filter::TRILINEAR, Wrap::REPEAT);
+
+    let expected: Color = texture.sample_gradient(0.3, 0.5, (0.01, 0.0), (0.0, 0.01));
+
+    for (dx, dy) in [((f32::NAN, 0.0), (0.0, 0.01)), ((0.01, 0.0), (f32::INFINITY, 0.0))]
+    {
+        let color: Color = texture.sample_gradient(0.3, 0.5, dx, dy);
+
+        assert_eq!(brightness(color), brightness(expected));
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{algebra::vector4::*, video::{clip::*, varyings::*}};
+
+fn varyings(x: f32, y: f32, z: f32, w: f32, value: f32) -> Varyings
+{
+    let mut varyings: Varyings = Varyings::create(Vector4::create(x, y, z, w));
+
+    // the last slot, nothing treats any of them differently
+    varyings.set(CAPACITY - 1, value);
+
+    varyings
+}
+
+#[test]
+fn perspective_correct()
+{
+    // an edge going away from the camera, the value follows the distance
+    let near: Varyings = varyings(-1.0, 0.0, 0.5, 1.0, 1.0);
+    let far: Varyings = varyings(3.0, 0.0, 2.5, 3.0, 3.0);
+
+    let (a, b) = (near.image_space(), far.image_space());
+
+    // halfway on the screen is a quarter of the way in 3D
+    let fragment: Varyings = Varyings::barycentric(&a, &b, &b, (0.5, 0.5, 0.0)).perspective_corrected();
+
+    assert!((fragment.get(CAPACITY - 1) - 1.5).abs() < 1e-6);
+
+    // the depth stays linear on the screen
+    assert!((fragment.position.z - (0.5 + 2.5 / 3.0) / 2.0).abs() < 1e-6);
+}
+
+#[test]
+fn clipped_varyings()
+{
+    // crosses the right plane (x = w) halfway between a & b, & between a & c
+    let a: Varyings = varyings(0.0, 0.0, 0.5, 1.0, 0.0);
+    let b: Varyings = varyings(2.0, 0.0, 0.5, 1.0, 10.0);
+    let c: Varyings = varyings(2.0, 0.5, 0.5, 1.0, 20.0);
+
+    let polygon: Vec<Varyings> = clip_triangle(&a, &b, &c);
+
+    assert_eq!(polygon.len(), 3);
+
+    let values: Vec<f32> = polygon.iter().map(|v| v.get(CAPACITY - 1)).collect();
+
+    assert_eq!(values, vec![0.0, 5.0, 10.0]);
+}
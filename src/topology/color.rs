@@ -17,6 +17,26 @@ impl Color
     {
         Self { r, g, b, }
     }
+
+    // packs the color into a 32-bit 0xRRGGBBAA value (components are clamped to [0, 1])
+    pub fn packed(&self) -> u32
+    {
+        let r: u32 = (self.r * 255.0) as u8 as u32;
+        let g: u32 = (self.g * 255.0) as u8 as u32;
+        let b: u32 = (self.b * 255.0) as u8 as u32;
+
+        (r << 24) | (g << 16) | (b << 8) | 0xFF
+    }
+
+    pub fn unpacked(rgba: u32) -> Self
+    {
+        Self
+        {
+            r: ((rgba >> 24) & 0xFF) as f32 / 255.0,
+            g: ((rgba >> 16) & 0xFF) as f32 / 255.0,
+            b: ((rgba >> 8) & 0xFF) as f32 / 255.0,
+        }
+    }
 }
 
 impl std::ops::Add<Color> for Color
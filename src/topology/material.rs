@@ -0,0 +1,159 @@
+use super::{color::*, model::ObjError, texture::*};
+
+use std::{collections::HashMap, fs, path::{Path, PathBuf}, rc::Rc};
+
+// describes how the surface of a part reacts to light
+pub struct Material
+{
+    // multiplies the vertex colors
+    pub diffuse: Color,
+    pub specular: Color,
+    pub shininess: f32,
+
+    // multiplies the scene's ambient light
+    pub ambient: Color,
+    // light given off by the surface itself, independent of any light source
+    pub emissive: Color,
+
+    // 1 is fully opaque, anything less is blended over what's already been drawn
+    pub opacity: f32,
+
+    // the maps multiply their matching color
+    pub diffuse_map: Option<Rc<Texture>>,
+    pub specular_map: Option<Rc<Texture>>,
+
+    // tangent-space normals
+    pub normal_map: Option<Rc<Texture>>,
+
+    // id of a shader added to the renderer, replaces the mesh's (or the renderer's) shader for this material
+    pub shader: Option<usize>,
+}
+
+impl Material
+{
+    pub fn create() -> Self
+    {
+        Self
+        {
+            diffuse: Color::create(1.0, 1.0, 1.0),
+            specular: Color::create(1.0, 1.0, 1.0),
+            shininess: 48.0,
+
+            ambient: Color::create(1.0, 1.0, 1.0),
+            emissive: Color::blank(),
+
+            opacity: 1.0,
+
+            diffuse_map: None,
+            specular_map: None,
+
+            normal_map: None,
+
+            shader: None,
+        }
+    }
+
+    pub fn textured(texture: Rc<Texture>) -> Self
+    {
+        Self { diffuse_map: Some(texture), ..Self::create() }
+    }
+
+    // parses a wavefront material library, the texture paths are relative to the library's directory
+    pub fn load_mtl<P: AsRef<Path>>(path: P) -> Result<HashMap<String, Material>, ObjError>
+    {
+        let path: &Path = path.as_ref();
+
+        let source: String = fs::read_to_string(path)?;
+
+        Self::read_mtl(&source, path.parent().unwrap_or(Path::new("")))
+    }
+
+    // parses a material library from memory, a bad number or a texture that fails to load is reported with its line
+    pub fn read_mtl(source: &str, directory: &Path) -> Result<HashMap<String, Material>, ObjError>
+    {
+        let mut materials: HashMap<String, Material> = HashMap::new();
+        let mut textures: HashMap<PathBuf, Rc<Texture>> = HashMap::new();
+
+        let mut current: Option<(String, Material)> = None;
+
+        for (i, text) in source.lines().enumerate()
+        {
+            let line: usize = i + 1;
+
+            let mut parts = text.split_whitespace();
+
+            let Some(token) = parts.next() else { continue; };
+
+            let arguments: Vec<&str> = parts.collect();
+
+            if token == "newmtl"
+            {
+                if let Some((name, material)) = current.take()
+                {
+                    materials.insert(name, material);
+                }
+
+                current = Some((arguments.join(" "), Material::create()));
+
+                continue;
+            }
+
+            let Some((_, material)) = current.as_mut() else { continue; };
+
+            let number = |i: usize| -> Result<f32, ObjError>
+            {
+                let argument: &str = arguments.get(i).ok_or_else(|| ObjError::PARSE { line, message: format!("expected {} numbers, found {}", i + 1, arguments.len()) })?;
+
+                argument.parse().map_err(|_| ObjError::PARSE { line, message: format!("invalid number '{}'", argument) })
+            };
+
+            // a single value stands for a gray color
+            let color = || -> Result<Color, ObjError>
+            {
+                if arguments.len() >= 3 { Ok(Color::create(number(0)?, number(1)?, number(2)?)) } else { Ok(Color::create(number(0)?, number(0)?, number(0)?)) }
+            };
+
+            // the file name comes after any options (-bm 1.0, -clamp on, ...), so it is the last argument
+            let mut texture = || -> Result<Option<Rc<Texture>>, ObjError>
+            {
+                let name: &str = arguments.last().ok_or_else(|| ObjError::PARSE { line, message: "expected a texture file".to_string() })?;
+
+                let file: PathBuf = directory.join(name);
+
+                if let Some(texture) = textures.get(&file) { return Ok(Some(texture.clone())); }
+
+                let texture: Rc<Texture> = Rc::new(Texture::load(&file).map_err(|error| ObjError::PARSE { line, message: format!("failed to load texture '{}' ({})", file.display(), error) })?);
+
+                textures.insert(file, texture.clone());
+
+                Ok(Some(texture))
+            };
+
+            match token
+            {
+                "Kd" => material.diffuse = color()?,
+                "Ks" => material.specular = color()?,
+                "Ka" => material.ambient = color()?,
+                "Ke" => material.emissive = color()?,
+                "Ns" => material.shininess = number(0)?,
+
+                // dissolve & its inverse, transparency
+                "d" => material.opacity = number(0)?,
+                "Tr" => material.opacity = 1.0 - number(0)?,
+
+                "map_Kd" => material.diffuse_map = texture()?,
+                "map_Ks" => material.specular_map = texture()?,
+                "map_Bump" | "map_bump" | "bump" | "norm" => material.normal_map = texture()?,
+
+                _ => { }
+            }
+        }
+
+        if let Some((name, material)) = current
+        {
+            materials.insert(name, material);
+        }
+
+        Ok(materials)
+    }
+}
\ No newline at end of file
@@ -1,7 +1,7 @@
 use super::{color::*, part::*, model::*, vertex::*};
-use crate::algebra::{matrix4x4::*, vector3::*};
+use crate::algebra::{matrix3x3::*, matrix4x4::*, quaternion::*, vector3::*};
 
-use std::f32::consts::PI;
+use std::{collections::HashMap, f32::consts::PI};
 
 //--------------------------------------------------------------------------//
 //                             == draw order ==
@@ -19,53 +19,91 @@ use std::f32::consts::PI;
 //   [0, 0] -> [1, 0] -> [1, 1]       ::       [1, 1] -> [0, 1] -> [0, 0]
 //--------------------------------------------------------------------------//
 
-pub struct Mesh
+// how much each face contributes to the normal of the vertices it shares with other faces
+#[derive(Clone, Copy, PartialEq)]
+pub enum Weighting { AREA, ANGLE }
+
+// the vertices can be of any format, the built-in one is the only one that can generate its normals & tangents
+pub struct Mesh<V = Vertex>
 {
     pub position: Vector3,
-    pub rotation: Vector3,
     pub scale: Vector3,
 
-    pub vertices: Vec<Vertex>,
+    // euler angles (in degrees) applied in the given order, then the orientation on top of them
+    pub rotation: Vector3,
+    pub order: Order,
+    pub orientation: Quaternion,
+
+    pub vertices: Vec<V>,
     pub indices: Vec<usize>,
     pub parts: Vec<Part>,
 
+    // id of a shader added to the renderer, replaces the renderer's shader for this mesh, a material's own shader still wins
+    pub shader: Option<usize>,
+
     pub model: Matrix4x4,
+
+    // inverse transpose of the model's upper 3x3, keeps the normals perpendicular to surfaces scaled unevenly
+    pub normal_matrix: Matrix3x3,
 }
 
-impl Mesh
+impl<V> Mesh<V>
 {
+    pub fn create(vertices: Vec<V>, indices: Vec<usize>, parts: Vec<Part>) -> Self
+    {
+        Self
+        {
+            position: Vector3::zero(),
+            rotation: Vector3::zero(),
+            scale: Vector3::create(1.0, 1.0, 1.0),
+
+            order: Order::XYZ,
+            orientation: Quaternion::identity(),
+
+            vertices,
+            indices,
+            parts,
+
+            shader: None,
+
+            model: Matrix4x4::identity(),
+            normal_matrix: Matrix3x3::identity(),
+        }
+    }
+
     pub fn update(&mut self)
     {
         self.model = 
         {
             Matrix4x4::translate(self.position.x, self.position.y, self.position.z) *
-            Matrix4x4::rotate_y(self.rotation.y) *
+            self.orientation.matrix() *
+            Matrix4x4::rotate(&self.rotation, self.order) *
             Matrix4x4::scale(self.scale.x, self.scale.y, self.scale.z)
-        }
+        };
+
+        // a singular model (zero scale) flattens the mesh anyway, its normals don't matter
+        self.normal_matrix = self.model.upper().inverse().map_or(self.model.upper(), |inverse| inverse.transpose());
     }
+}
 
+impl Mesh
+{
     pub fn converted(model: &Model) -> Self
     {
-        let mut mesh = Self
-        { 
-            position: Vector3::zero(),
-            rotation: Vector3::zero(),
-            scale: Vector3::create(1.0, 1.0, 1.0),
+        let mut mesh: Mesh = Mesh::create(model.vertices.clone(), model.indices.clone(), Vec::new());
 
-            vertices: model.vertices.clone(),
-            indices: Vec::new(), 
-            parts: vec![Part::create(Topology::TRIANGLE, 0, model.vertices.len() / 3)],
+        // one part per material group
+        for group in &model.groups
+        {
+            let mut part: Part = Part::create(Topology::TRIANGLE, group.index, group.count);
 
-            model: Matrix4x4::identity(),
-        };
+            part.material = group.material.clone();
 
-        for i in 0..model.vertices.len() / 3
-        {
-            mesh.indices.push(i * 3);
-            mesh.indices.push(i * 3 + 1);
-            mesh.indices.push(i * 3 + 2);
+            mesh.parts.push(part);
         }
 
+        mesh.calculate_tangents();
+
         mesh
     }
 
@@ -88,6 +126,293 @@ impl Mesh
             self.vertices[self.indices[i + 1]].normal = normal;
             self.vertices[self.indices[i + 2]].normal = normal;
         }
+
+        // the tangents are built around the normals
+        self.calculate_tangents();
+    }
+
+    // averages the normals of the faces around every vertex, faces meeting at an angle sharper than the crease angle (in degrees)
+    // don't smooth each other, the vertices on such hard edges are split so each side keeps its own normal
+    pub fn smooth_normals(&mut self, crease: f32, weighting: Weighting, opposite: bool)
+    {
+        Mesh::generate_normals(&mut self.vertices, &mut self.indices, crease, weighting, opposite, false);
+
+        self.calculate_tangents();
+    }
+
+    // shared by the meshes & the model loader, which only fills in the normals that are missing (zero)
+    pub fn generate_normals(vertices: &mut Vec<Vertex>, indices: &mut [usize], crease: f32, weighting: Weighting, opposite: bool, missing_only: bool)
+    {
+        // adding zero turns -0 into 0, so the poles of a sphere (sin(0) times a negative cosine) count as one position
+        let position = |v: &Vertex| -> [u32; 3] { [v.x, v.y, v.z].map(|c| (c + 0.0).to_bits()) };
+
+        let triangles: usize = indices.len() / 3;
+
+        // unit normal of every face & the weight of each of its corners
+        let mut normals: Vec<Vector3> = Vec::with_capacity(triangles);
+        let mut weights: Vec<[f32; 3]> = Vec::with_capacity(triangles);
+
+        // the faces (& which of their corners) touching every position, vertices split by uv or color still smooth together
+        let mut corners: HashMap<[u32; 3], Vec<(usize, usize)>> = HashMap::new();
+
+        for t in 0 .. triangles
+        {
+            let p: [Vector3; 3] = [0, 1, 2].map(|k| { let v: &Vertex = &vertices[indices[t * 3 + k]]; Vector3::create(v.x, v.y, v.z) });
+
+            let mut cross: Vector3 = Vector3::cross(&(p[1] - p[0]), &(p[2] - p[0]));
+
+            if opposite { cross = cross.opposite(); }
+
+            // twice the area of the face
+            let area: f32 = cross.length();
+
+            normals.push(if area > 0.0 { cross / area } else { Vector3::zero() });
+
+            weights.push(match weighting
+            {
+                Weighting::AREA => [area; 3],
+
+                // the angle between the two edges leaving each corner
+                Weighting::ANGLE => [0, 1, 2].map(|k|
+                {
+                    let a: Vector3 = p[(k + 1) % 3] - p[k];
+                    let b: Vector3 = p[(k + 2) % 3] - p[k];
+
+                    let cosine: f32 = Vector3::dot(&a, &b) / (a.length() * b.length());
+
+                    if cosine.is_nan() { 0.0 } else { cosine.clamp(-1.0, 1.0).acos() }
+                }),
+            });
+
+            for k in 0 .. 3
+            {
+                corners.entry(position(&vertices[indices[t * 3 + k]])).or_default().push((t, k));
+            }
+        }
+
+        let threshold: f32 = f32::cos(f32::to_radians(crease));
+
+        // decided up front, as the pass below fills in the normals
+        let skipped: Vec<bool> = vertices.iter().map(|v| missing_only && v.normal.length() > 0.0).collect();
+
+        // the normal each vertex got first & the copies made for the corners that need a different one
+        let mut assigned: Vec<Option<Vector3>> = vec![None; vertices.len()];
+        let mut splits: HashMap<(usize, [u32; 3]), usize> = HashMap::new();
+
+        for t in 0 .. triangles
+        {
+            for k in 0 .. 3
+            {
+                let i: usize = indices[t * 3 + k];
+
+                if skipped[i] { continue; }
+
+                let mut sum: Vector3 = Vector3::zero();
+
+                for (other, corner) in &corners[&position(&vertices[i])]
+                {
+                    if Vector3::dot(&normals[t], &normals[*other]) >= threshold
+                    {
+                        sum += normals[*other] * weights[*other][*corner];
+                    }
+                }
+
+                let normal: Vector3 = if sum.length() > 0.0 { sum.normalized() } else { normals[t] };
+
+                match assigned[i]
+                {
+                    None =>
+                    {
+                        assigned[i] = Some(normal);
+                        vertices[i].normal = normal;
+                    }
+
+                    Some(existing) if (existing - normal).length() <= 1e-6 => { }
+
+                    // this corner is on the other side of a hard edge, so it gets its own vertex
+                    Some(_) =>
+                    {
+                        let copy: usize = *splits.entry((i, [normal.x, normal.y, normal.z].map(f32::to_bits))).or_insert_with(||
+                        {
+                            vertices.push(Vertex { normal, ..vertices[i] });
+                            assigned.push(Some(normal));
+
+                            vertices.len() - 1
+                        });
+
+                        indices[t * 3 + k] = copy;
+                    }
+                }
+            }
+        }
+    }
+
+    // computes the tangent space of every vertex from the positions & uvs of the faces around it: the face tangents are
+    // projected onto the vertex normal's plane & weighted by the corner angle, the bitangent is rebuilt from the normal &
+    // tangent with the handedness of the uv mapping, the vertices shared by faces of both handedness (on the seam of
+    // mirrored uvs) are split so each side gets its own tangent instead of the two cancelling out
+    pub fn calculate_tangents(&mut self)
+    {
+        // removes the part of a direction along the normal
+        let project = |direction: Vector3, normal: &Vector3| -> Vector3
+        {
+            let projected: Vector3 = direction - *normal * Vector3::dot(normal, &direction);
+
+            if projected.length() > 0.0 { projected.normalized() } else { Vector3::zero() }
+        };
+
+        // the uv determinant of a triangle, negative when its uvs are mirrored
+        let uv_determinant = |vertices: &[Vertex], triangle: &[usize]| -> f32
+        {
+            let [a, b, c] = [0, 1, 2].map(|k| &vertices[triangle[k]]);
+
+            (b.u - a.u) * (c.v - a.v) - (c.u - a.u) * (b.v - a.v)
+        };
+
+        // only the triangles the parts actually draw, by their first index
+        let starts: Vec<usize> = self.parts.iter().flat_map(|part| (part.index .. part.index + part.count * 3).step_by(3)).collect();
+
+        // 1 when a vertex is used by a face with the regular handedness, 2 with the mirrored one
+        let mut handedness: Vec<u8> = vec![0; self.vertices.len()];
+
+        for start in &starts
+        {
+            let side: u8 = match uv_determinant(&self.vertices, &self.indices[*start .. *start + 3])
+            {
+                d if d > 0.0 => 1,
+                d if d < 0.0 => 2,
+                _ => continue,
+            };
+
+            for k in 0 .. 3
+            {
+                handedness[self.indices[start + k]] |= side;
+            }
+        }
+
+        // the mirrored faces move to a copy of the vertices they share with the regular ones
+        let mut copies: Vec<Option<usize>> = vec![None; self.vertices.len()];
+
+        for start in &starts
+        {
+            if uv_determinant(&self.vertices, &self.indices[*start .. *start + 3]) >= 0.0 { continue; }
+
+            for k in 0 .. 3
+            {
+                let index: usize = self.indices[start + k];
+
+                if handedness[index] != 3 { continue; }
+
+                let vertices: &mut Vec<Vertex> = &mut self.vertices;
+
+                self.indices[start + k] = *copies[index].get_or_insert_with(||
+                {
+                    vertices.push(vertices[index]);
+
+                    vertices.len() - 1
+                });
+            }
+        }
+
+        let mut tangents: Vec<Vector3> = vec![Vector3::zero(); self.vertices.len()];
+        let mut bitangents: Vec<Vector3> = vec![Vector3::zero(); self.vertices.len()];
+
+        let triangles = starts.iter().map(|start| &self.indices[*start .. *start + 3]);
+
+        for triangle in triangles
+        {
+            let corners: [&Vertex; 3] = [0, 1, 2].map(|k| &self.vertices[triangle[k]]);
+            let positions: [Vector3; 3] = corners.map(|v| Vector3::create(v.x, v.y, v.z));
+
+            let (e1, e2) = (positions[1] - positions[0], positions[2] - positions[0]);
+
+            let (du1, dv1) = (corners[1].u - corners[0].u, corners[1].v - corners[0].v);
+            let (du2, dv2) = (corners[2].u - corners[0].u, corners[2].v - corners[0].v);
+
+            let determinant: f32 = du1 * dv2 - du2 * dv1;
+
+            // the uvs don't span an area, there is no direction to follow
+            if determinant == 0.0 { continue; }
+
+            let tangent: Vector3 = (e1 * dv2 - e2 * dv1) / determinant;
+            let bitangent: Vector3 = (e2 * du1 - e1 * du2) / determinant;
+
+            for k in 0 .. 3
+            {
+                let a: Vector3 = positions[(k + 1) % 3] - positions[k];
+                let b: Vector3 = positions[(k + 2) % 3] - positions[k];
+
+                let cosine: f32 = Vector3::dot(&a, &b) / (a.length() * b.length());
+
+                if cosine.is_nan() { continue; }
+
+                let angle: f32 = cosine.clamp(-1.0, 1.0).acos();
+
+                let normal: Vector3 = corners[k].normal.normalized();
+
+                tangents[triangle[k]] += project(tangent, &normal) * angle;
+                bitangents[triangle[k]] += project(bitangent, &normal) * angle;
+            }
+        }
+
+        for (vertex, (tangent, bitangent)) in self.vertices.iter_mut().zip(tangents.into_iter().zip(bitangents))
+        {
+            let normal: Vector3 = vertex.normal.normalized();
+
+            vertex.tangent = project(tangent, &normal);
+
+            let handedness: f32 = if Vector3::dot(&Vector3::cross(&normal, &vertex.tangent), &bitangent) < 0.0 { -1.0 } else { 1.0 };
+
+            vertex.bitangent = Vector3::cross(&normal, &vertex.tangent) * handedness;
+        }
+    }
+
+    // merges the vertices whose attributes (position, color, uv & normal) all lie within epsilon of each other
+    pub fn weld(&mut self, epsilon: f32)
+    {
+        let size: f32 = if epsilon > 0.0 { epsilon } else { 1.0 };
+
+        // vertices closer than the cell size can only be in the same or a neighboring cell
+        let cell = |v: &Vertex| -> (i64, i64, i64) { ((v.x / size).floor() as i64, (v.y / size).floor() as i64, (v.z / size).floor() as i64) };
+
+        let close = |a: &Vertex, b: &Vertex| -> bool
+        {
+            [a.x - b.x, a.y - b.y, a.z - b.z, a.u - b.u, a.v - b.v, a.color.r - b.color.r, a.color.g - b.color.g, a.color.b - b.color.b, a.normal.x - b.normal.x, a.normal.y - b.normal.y, a.normal.z - b.normal.z]
+                .iter()
+                .all(|d| d.abs() <= epsilon)
+        };
+
+        let mut grid: HashMap<(i64, i64, i64), Vec<usize>> = HashMap::new();
+
+        let mut vertices: Vec<Vertex> = Vec::new();
+        let mut remap: Vec<usize> = Vec::with_capacity(self.vertices.len());
+
+        for vertex in &self.vertices
+        {
+            let (x, y, z) = cell(vertex);
+
+            let neighbors = (-1 ..= 1).flat_map(|i| (-1 ..= 1).flat_map(move |j| (-1 ..= 1).map(move |k| (x + i, y + j, z + k))));
+
+            let existing: Option<usize> = neighbors.filter_map(|key| grid.get(&key)).flatten().copied().find(|i| close(&vertices[*i], vertex));
+
+            remap.push(existing.unwrap_or_else(||
+            {
+                vertices.push(*vertex);
+                grid.entry((x, y, z)).or_default().push(vertices.len() - 1);
+
+                vertices.len() - 1
+            }));
+        }
+
+        for index in &mut self.indices
+        {
+            *index = remap[*index];
+        }
+
+        self.vertices = vertices;
+
+        // the merged vertices kept the tangents of the first one
+        self.calculate_tangents();
     }
 
     //--------------------------------------------------------------------------//
@@ -95,13 +420,7 @@ impl Mesh
     //--------------------------------------------------------------------------//
     pub fn triangle() -> Self
     {
-        let mut triangle = Self
-        { 
-            position: Vector3::zero(), rotation: Vector3::zero(), scale: Vector3::create(1.0, 1.0, 1.0),
-            vertices: Vec::new(), indices: Vec::new(), parts: Vec::new(),
-
-            model: Matrix4x4::identity(),
-        };
+        let mut triangle: Mesh = Mesh::create(Vec::new(), Vec::new(), Vec::new());
         
         triangle.vertices.push(Vertex::create(-0.5, -0.25, 0.0, Color::create(0.0, 0.0, 1.0), 0.0, 0.0));
         triangle.vertices.push(Vertex::create(0.5, -0.25, 0.0, Color::create(1.0, 1.0, 0.0), 1.0, 0.0));
@@ -116,13 +435,7 @@ impl Mesh
 
     pub fn plane(color: &Color) -> Self
     {
-        let mut plane = Self
-        { 
-            position: Vector3::zero(), rotation: Vector3::zero(), scale: Vector3::create(1.0, 1.0, 1.0),
-            vertices: Vec::new(), indices: Vec::new(), parts: Vec::new(),
-
-            model: Matrix4x4::identity(),
-        };
+        let mut plane: Mesh = Mesh::create(Vec::new(), Vec::new(), Vec::new());
 
         plane.vertices = vec!
         [
@@ -147,13 +460,7 @@ impl Mesh
 
     pub fn cube() -> Self
     {
-        let mut cube = Self
-        { 
-            position: Vector3::zero(), rotation: Vector3::zero(), scale: Vector3::create(1.0, 1.0, 1.0),
-            vertices: Vec::new(), indices: Vec::new(), parts: Vec::new(),
-
-            model: Matrix4x4::identity(),
-        };
+        let mut cube: Mesh = Mesh::create(Vec::new(), Vec::new(), Vec::new());
 
         cube.vertices = vec!
         [
@@ -224,32 +531,33 @@ impl Mesh
 
     pub fn sphere(segments: usize, rings: usize, radius: f32) -> Self
     {
-        let mut sphere = Self
-        { 
-            position: Vector3::zero(), rotation: Vector3::zero(), scale: Vector3::create(1.0, 1.0, 1.0),
-            vertices: Vec::new(), indices: Vec::new(), parts: Vec::new(),
-
-            model: Matrix4x4::identity(),
-        };
+        let mut sphere: Mesh = Mesh::create(Vec::new(), Vec::new(), Vec::new());
 
-        for ring in 0 .. rings * 2
+        // the first & last ring are the poles, the seam's vertices are doubled so the uvs can wrap
+        for ring in 0 ..= rings
         {
-            // polar angle in spherical coordinates
+            // polar angle in spherical coordinates, the poles are exactly on the axis (sin(PI) isn't quite 0)
             let theta: f32 = ring as f32 / rings as f32 * PI;
+            let sine: f32 = if ring == 0 || ring == rings { 0.0 } else { f32::sin(theta) };
 
             for segment in 0 ..= segments
             {
-                // azimuthal angle in spherical coordinates
-                let phi: f32 = segment as f32 / segments as f32 * 2.0 * PI;
+                // azimuthal angle in spherical coordinates, both sides of the seam share the same position
+                let phi: f32 = (segment % segments) as f32 / segments as f32 * 2.0 * PI;
 
-                // vertices
-                let x: f32 = radius * f32::sin(theta) * f32::cos(phi);
+                let x: f32 = radius * sine * f32::cos(phi);
                 let y: f32 = radius * f32::cos(theta);
-                let z: f32 = radius * f32::sin(theta) * f32::sin(phi);
+                let z: f32 = radius * sine * f32::sin(phi);
 
                 sphere.vertices.push(Vertex::full(x, y, z, Color::create(0.2, 0.4, 0.8), segment as f32 / segments as f32, 1.0 - (ring as f32 / rings as f32), Vector3::create(x, y, z).normalized()));
+            }
+        }
 
-                // indices
+        // two triangles between each pair of neighboring rings & segments
+        for ring in 0 .. rings
+        {
+            for segment in 0 .. segments
+            {
                 let current: usize = ring * (segments + 1) + segment;
                 let next: usize = current + segments + 1;
 
@@ -263,7 +571,9 @@ impl Mesh
             }
         }
 
-        sphere.parts = vec![Part::create(Topology::TRIANGLE, 0, sphere.vertices.len())];
+        sphere.parts = vec![Part::create(Topology::TRIANGLE, 0, sphere.indices.len() / 3)];
+
+        sphere.calculate_tangents();
 
         sphere
     }
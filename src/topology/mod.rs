@@ -1,5 +1,7 @@
 pub mod color;
 pub mod vertex;
 pub mod part;
+pub mod texture;
+pub mod material;
 pub mod mesh;
 pub mod model;
\ No newline at end of file
@@ -1,133 +1,416 @@
 use super::color::Color;
+use super::material::*;
 use super::vertex::*;
+use super::mesh::*;
 use crate::algebra::vector3::*;
 
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::rc::Rc;
+
+// a run of consecutive triangles that share a material (usemtl)
+pub struct Group
+{
+    pub material: Rc<Material>,
+
+    // first index & number of triangles
+    pub index: usize,
+    pub count: usize,
+}
 
 pub struct Model
 {
+    // every distinct position, uv & normal combination is stored once
     pub vertices: Vec<Vertex>,
+    pub indices: Vec<usize>,
+    pub groups: Vec<Group>,
+}
+
+#[derive(Debug)]
+pub enum ObjError
+{
+    IO(io::Error),
+
+    // the line number starts at 1
+    PARSE { line: usize, message: String },
+}
+
+impl std::fmt::Display for ObjError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            ObjError::IO(error) => write!(f, "failed to read file ({})", error),
+            ObjError::PARSE { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for ObjError { }
+
+impl From<io::Error> for ObjError
+{
+    fn from(error: io::Error) -> Self
+    {
+        ObjError::IO(error)
+    }
+}
+
+// faces of a model without normals meeting at a sharper angle (in degrees) keep a hard edge
+pub const CREASE_ANGLE: f32 = 60.0;
+
+// a corner of a face, as zero-based indices into the positions, uvs & normals
+#[derive(Clone, Copy)]
+struct Corner
+{
+    v: usize,
+    t: Option<usize>,
+    n: Option<usize>,
+}
+
+// the vertex data read so far
+struct Attributes
+{
+    positions: Vec<Vector3>,
+    normals: Vec<Vector3>,
+    uvs: Vec<Vector3>,
+}
+
+fn error(line: usize, message: String) -> ObjError
+{
+    ObjError::PARSE { line, message }
+}
+
+fn numbers(line: usize, arguments: &[&str], count: usize) -> Result<Vec<f32>, ObjError>
+{
+    if arguments.len() < count
+    {
+        return Err(error(line, format!("expected {} numbers, found {}", count, arguments.len())));
+    }
+
+    arguments[.. count].iter().map(|a| a.parse::<f32>().map_err(|_| error(line, format!("invalid number '{}'", a)))).collect()
+}
+
+// resolves a one-based index, negative indices count backwards from the most recent element
+fn index(line: usize, text: &str, count: usize) -> Result<usize, ObjError>
+{
+    let i: i64 = text.parse().map_err(|_| error(line, format!("invalid index '{}'", text)))?;
+
+    let resolved: i64 = if i < 0 { count as i64 + i } else { i - 1 };
+
+    if i == 0 || resolved < 0 || resolved >= count as i64
+    {
+        return Err(error(line, format!("index {} is out of range (there are {} elements)", i, count)));
+    }
+
+    Ok(resolved as usize)
 }
 
-struct Face
+// v, v/t, v//n or v/t/n
+fn corner(line: usize, text: &str, attributes: &Attributes) -> Result<Corner, ObjError>
 {
-    v1: usize, v2: usize, v3: usize,
-    t1: usize, t2: usize, t3: usize,
-    n1: usize, n2: usize, n3: usize,
+    let mut fields = text.split('/');
+
+    let v: usize = index(line, fields.next().unwrap_or(""), attributes.positions.len())?;
+
+    let t: Option<usize> = match fields.next()
+    {
+        Some(field) if !field.is_empty() => Some(index(line, field, attributes.uvs.len())?),
+        _ => None,
+    };
+
+    let n: Option<usize> = match fields.next()
+    {
+        Some(field) if !field.is_empty() => Some(index(line, field, attributes.normals.len())?),
+        _ => None,
+    };
+
+    if fields.next().is_some()
+    {
+        return Err(error(line, format!("invalid face vertex '{}'", text)));
+    }
+
+    Ok(Corner { v, t, n })
+}
+
+// joins the lines ending with a backslash & strips the comments, keeping the number of the line each statement starts on
+fn statements<R: BufRead>(reader: R) -> Result<Vec<(usize, String)>, ObjError>
+{
+    let mut statements: Vec<(usize, String)> = Vec::new();
+    let mut pending: Option<(usize, String)> = None;
+
+    for (i, line) in reader.lines().enumerate()
+    {
+        let line: String = line?;
+
+        let line: &str = match line.find('#')
+        {
+            Some(comment) => &line[.. comment],
+            None => &line,
+        };
+
+        let (number, mut text) = pending.take().unwrap_or((i + 1, String::new()));
+
+        match line.trim_end().strip_suffix('\\')
+        {
+            Some(continued) =>
+            {
+                text.push_str(continued);
+                text.push(' ');
+
+                pending = Some((number, text));
+            }
+
+            None =>
+            {
+                text.push_str(line);
+
+                statements.push((number, text));
+            }
+        }
+    }
+
+    if let Some(statement) = pending
+    {
+        statements.push(statement);
+    }
+
+    Ok(statements)
+}
+
+// splits a planar polygon into triangles that keep its winding order, returned as indices into the polygon
+// convex polygons are split into a fan, concave ones are cut down one ear at a time
+fn triangulate(polygon: &[Vector3]) -> Vec<[usize; 3]>
+{
+    let count: usize = polygon.len();
+
+    let fan = |indices: &[usize]| -> Vec<[usize; 3]> { (1 .. indices.len() - 1).map(|i| [indices[0], indices[i], indices[i + 1]]).collect() };
+
+    if count == 3 { return vec![[0, 1, 2]]; }
+
+    // Newell's method gives the polygon normal even when some of the corners are concave
+    let mut normal: Vector3 = Vector3::zero();
+
+    for i in 0 .. count
+    {
+        let (a, b) = (polygon[i], polygon[(i + 1) % count]);
+
+        normal += Vector3::create((a.y - b.y) * (a.z + b.z), (a.z - b.z) * (a.x + b.x), (a.x - b.x) * (a.y + b.y));
+    }
+
+    // project onto the plane the polygon faces the most, the normal's sign keeps the winding counter-clockwise
+    let (x, y, z) = (normal.x.abs(), normal.y.abs(), normal.z.abs());
+
+    let points: Vec<(f32, f32)> = polygon.iter().map(|p|
+    {
+        if x >= y && x >= z { (p.y, p.z * normal.x.signum()) }
+        else if y >= z { (p.z, p.x * normal.y.signum()) }
+        else { (p.x, p.y * normal.z.signum()) }
+    })
+    .collect();
+
+    // twice the signed area of a triangle, positive when counter-clockwise
+    let cross = |a: usize, b: usize, c: usize| -> f32
+    {
+        (points[b].0 - points[a].0) * (points[c].1 - points[a].1) - (points[b].1 - points[a].1) * (points[c].0 - points[a].0)
+    };
+
+    let mut remaining: Vec<usize> = (0 .. count).collect();
+
+    if (0 .. count).all(|i| cross(i, (i + 1) % count, (i + 2) % count) >= 0.0) { return fan(&remaining); }
+
+    let mut triangles: Vec<[usize; 3]> = Vec::with_capacity(count - 2);
+
+    while remaining.len() > 3
+    {
+        let n: usize = remaining.len();
+
+        // an ear is a convex corner whose triangle contains none of the other corners
+        let ear: Option<usize> = (0 .. n).find(|i|
+        {
+            let (a, b, c) = (remaining[(i + n - 1) % n], remaining[*i], remaining[(i + 1) % n]);
+
+            cross(a, b, c) > 0.0 && remaining.iter().filter(|p| ![a, b, c].contains(p)).all(|p| cross(a, b, *p) < 0.0 || cross(b, c, *p) < 0.0 || cross(c, a, *p) < 0.0)
+        });
+
+        match ear
+        {
+            Some(i) =>
+            {
+                triangles.push([remaining[(i + n - 1) % n], remaining[i], remaining[(i + 1) % n]]);
+                remaining.remove(i);
+            }
+
+            // degenerate (self-intersecting or collinear) polygons fall back to a fan
+            None => break,
+        }
+    }
+
+    triangles.extend(fan(&remaining));
+
+    triangles
 }
 
 impl Model
 {
-    pub fn load_obj(path: &str, uv_factor: f32) -> Self
+    pub fn load_obj<P: AsRef<Path>>(path: P, uv_factor: f32) -> Result<Self, ObjError>
     {
-        let mut model = Self { vertices: Vec::new() };
+        let path: &Path = path.as_ref();
+
+        let file = File::open(path)?;
+
+        Self::read_obj(BufReader::new(file), path.parent().unwrap_or(Path::new("")), uv_factor)
+    }
+
+    // parses an OBJ from any source, material libraries are looked up relative to the directory
+    pub fn read_obj<R: BufRead>(reader: R, directory: &Path, uv_factor: f32) -> Result<Self, ObjError>
+    {
+        let mut model = Self { vertices: Vec::new(), indices: Vec::new(), groups: Vec::new() };
+
+        // index of the vertex already created for a given set of attributes
+        let mut welded: HashMap<[u32; 8], usize> = HashMap::new();
+
+        // used by the faces that come before any usemtl, or that name a material that doesn't exist
+        let default_material: Rc<Material> = Rc::new(Material { diffuse: Color::create(0.5, 0.5, 0.5), ..Material::create() });
 
-        let file = File::open(path).expect("Error : failed to open file");
-        let reader = BufReader::new(file);
+        let mut library: HashMap<String, Rc<Material>> = HashMap::new();
 
-        let mut positions = Vec::new();
-        let mut normals = Vec::new();
-        let mut uvs = Vec::new();
+        let mut attributes = Attributes { positions: Vec::new(), normals: Vec::new(), uvs: Vec::new() };
 
-        for line in reader.lines()
+        for (line, statement) in statements(reader)?
         {
-            if let Ok(line) = line
+            let mut parts = statement.split_whitespace();
+
+            let Some(token) = parts.next() else { continue; };
+
+            let arguments: Vec<&str> = parts.collect();
+
+            match token
             {
-                let mut parts = line.split_whitespace();
+                // material libraries are relative to the model
+                "mtllib" =>
+                {
+                    for name in &arguments
+                    {
+                        let file = directory.join(name);
 
-                if let Some(token) = parts.next()
+                        let materials: HashMap<String, Material> = Material::load_mtl(&file).map_err(|e| error(line, format!("failed to load material library '{}' ({})", file.display(), e)))?;
+
+                        library.extend(materials.into_iter().map(|(name, material)| (name, Rc::new(material))));
+                    }
+                }
+
+                "usemtl" =>
                 {
-                    match token
+                    let name: String = arguments.join(" ");
+
+                    let material: Rc<Material> = library.get(&name).cloned().unwrap_or_else(|| default_material.clone());
+
+                    model.groups.push(Group { material, index: model.indices.len(), count: 0 });
+                }
+
+                "v" =>
+                {
+                    let v: Vec<f32> = numbers(line, &arguments, 3)?;
+
+                    attributes.positions.push(Vector3::create(v[0], v[1], v[2]));
+                }
+
+                "vn" =>
+                {
+                    let n: Vec<f32> = numbers(line, &arguments, 3)?;
+
+                    attributes.normals.push(Vector3::create(n[0], n[1], n[2]));
+                }
+
+                // the third (depth) coordinate is optional & unused
+                "vt" =>
+                {
+                    let t: Vec<f32> = numbers(line, &arguments, if arguments.len() == 1 { 1 } else { 2 })?;
+
+                    attributes.uvs.push(Vector3::create(t[0], t.get(1).copied().unwrap_or(0.0), 0.0));
+                }
+
+                "f" =>
+                {
+                    let corners: Vec<Corner> = arguments.iter().map(|a| corner(line, a, &attributes)).collect::<Result<Vec<Corner>, ObjError>>()?;
+
+                    if corners.len() < 3
                     {
-                        "v" =>
-                        {
-                            let x: f32 = parts.next().unwrap().parse().unwrap();
-                            let y: f32 = parts.next().unwrap().parse().unwrap();
-                            let z: f32 = parts.next().unwrap().parse().unwrap();
-
-                            positions.push(Vector3::create(x, y, z));
-                        }
-
-                        "vn" =>
-                        {
-                            let x: f32 = parts.next().unwrap().parse().unwrap();
-                            let y: f32 = parts.next().unwrap().parse().unwrap();
-                            let z: f32 = parts.next().unwrap().parse().unwrap();
-
-                            normals.push(Vector3::create(x, y, z));
-                        }
-
-                        "vt" =>
-                        {
-                            let x: f32 = parts.next().unwrap().parse().unwrap();
-                            let y: f32 = parts.next().unwrap().parse().unwrap();
-
-                            uvs.push(Vector3::create(x, y, 0.0));
-                        }
-
-                        "f" =>
-                        {
-                            let mut face = Face
-                            {
-                                v1: 0, t1: 0, n1: 0,
-                                v2: 0, t2: 0, n2: 0,
-                                v3: 0, t3: 0, n3: 0,
-                            };
-
-                            let mut indices = Vec::new();
-
-                            for part in parts
-                            {
-                                let mut subparts = part.split('/');
-
-                                let v: usize = subparts.next().unwrap().parse().unwrap();
-                                let t: usize = subparts.next().unwrap().parse().unwrap();
-                                let n: usize = subparts.next().unwrap().parse().unwrap();
-                                
-                                indices.push((v, t, n));
-                            }
-
-                            face.v1 = indices[0].0;
-                            face.t1 = indices[0].1;
-                            face.n1 = indices[0].2;
-
-                            face.v2 = indices[1].0;
-                            face.t2 = indices[1].1;
-                            face.n2 = indices[1].2;
-                            
-                            face.v3 = indices[2].0;
-                            face.t3 = indices[2].1;
-                            face.n3 = indices[2].2;
-
-                            model.process(face.v1, face.t1, face.n1, &positions, &normals, &uvs, uv_factor);
-                            model.process(face.v2, face.t2, face.n2, &positions, &normals, &uvs, uv_factor);
-                            model.process(face.v3, face.t3, face.n3, &positions, &normals, &uvs, uv_factor);
-                        }
-
-                        _ => { }
+                        return Err(error(line, format!("a face needs at least 3 vertices, found {}", corners.len())));
                     }
+
+                    let positions: Vec<Vector3> = corners.iter().map(|c| attributes.positions[c.v]).collect();
+
+                    let triangles: Vec<[usize; 3]> = triangulate(&positions);
+
+                    for [a, b, c] in &triangles
+                    {
+                        model.process([corners[*a], corners[*b], corners[*c]], &attributes, uv_factor, &mut welded);
+                    }
+
+                    if model.groups.is_empty()
+                    {
+                        model.groups.push(Group { material: default_material.clone(), index: 0, count: 0 });
+                    }
+
+                    model.groups.last_mut().unwrap().count += triangles.len();
                 }
+
+                _ => { }
             }
         }
 
-        model
+        model.groups.retain(|group| group.count > 0);
+
+        // the mirrored x flips the winding, hence the opposite normals
+        if model.vertices.iter().any(|v| v.normal.length() == 0.0)
+        {
+            Mesh::generate_normals(&mut model.vertices, &mut model.indices, CREASE_ANGLE, Weighting::AREA, true, true);
+        }
+
+        Ok(model)
     }
 
-    fn process(&mut self, v: usize, t: usize, n: usize, positions: &Vec<Vector3>, normals: &Vec<Vector3>, uvs: &Vec<Vector3>, uv_factor: f32)
+    fn process(&mut self, triangle: [Corner; 3], attributes: &Attributes, uv_factor: f32, welded: &mut HashMap<[u32; 8], usize>)
     {
-        let mut vertex: Vertex = Vertex::partial
-        (
-            -positions[v -1].x, positions[v -1].y, positions[v -1].z, // note the x coordinate
-            uvs[t - 1].x, uvs[t - 1].y, 
-            normals[n - 1]
-        );
-        
-        vertex.color = Color::create(0.5, 0.5, 0.5);
+        for corner in &triangle
+        {
+            let position: Vector3 = attributes.positions[corner.v];
+            let uv: Vector3 = corner.t.map_or(Vector3::zero(), |t| attributes.uvs[t]);
+
+            // corners without a normal are left at zero & get a smoothed one once the whole model is read
+            let normal: Vector3 = corner.n.map_or(Vector3::zero(), |n| attributes.normals[n]);
+
+            let mut vertex: Vertex = Vertex::partial
+            (
+                -position.x, position.y, position.z, // note the x coordinate
+                uv.x, uv.y,
+                // the normals are directions in the same space as the positions, so they change hands too, otherwise
+                // they'd point out of the other side of the surface & left-facing sides would be lit from the right
+                Vector3::create(-normal.x, normal.y, normal.z)
+            );
+
+            // the surface color comes from the material
+            vertex.color = Color::create(1.0, 1.0, 1.0);
+
+            vertex.u *= uv_factor;
+            vertex.v *= uv_factor;
 
-        vertex.u *= uv_factor;
-        vertex.v *= uv_factor;
+            // reuse the vertex if an identical one was already created
+            let key: [u32; 8] = [vertex.x, vertex.y, vertex.z, vertex.u, vertex.v, vertex.normal.x, vertex.normal.y, vertex.normal.z].map(f32::to_bits);
 
-        self.vertices.push(vertex);
+            let index: usize = *welded.entry(key).or_insert_with(||
+            {
+                self.vertices.push(vertex);
+
+                self.vertices.len() - 1
+            });
+
+            self.indices.push(index);
+        }
     }
-}
\ No newline at end of file
+}
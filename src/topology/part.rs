@@ -1,3 +1,7 @@
+use super::material::*;
+
+use std::rc::Rc;
+
 pub enum Topology { TRIANGLE }
 
 pub struct Part
@@ -5,12 +9,15 @@ pub struct Part
     pub index: usize,
     pub count: usize,
     pub topology: Topology,
+
+    // shared, so several parts (or meshes) can use the same material
+    pub material: Rc<Material>,
 }
 
 impl Part
 {
     pub fn create(topology: Topology, index: usize, count: usize) -> Self
     {
-        Self { index, count, topology }
+        Self { index, count, topology, material: Rc::new(Material::create()) }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,231 @@
+use super::color::*;
+use crate::image::bitmap::*;
+
+use std::{io, path::Path};
+
+// NEAREST & BILINEAR always sample the full size image, TRILINEAR blends between the two closest mipmaps
+#[derive(Clone, Copy, PartialEq)]
+pub enum Filter { NEAREST, BILINEAR, TRILINEAR }
+
+// how the coordinates outside of [0, 1] are mapped back onto the texture
+#[derive(Clone, Copy, PartialEq)]
+pub enum Wrap { REPEAT, CLAMP, MIRROR }
+
+// a single image of the mip chain
+pub struct Level
+{
+    pub width: i32,
+    pub height: i32,
+
+    // texels in row-major order, the first row is the top of the image (v = 1)
+    pub texels: Vec<Color>,
+}
+
+pub struct Texture
+{
+    // the full size image followed by its mipmaps, each half the size of the previous one, down to 1x1
+    pub levels: Vec<Level>,
+
+    pub filter: Filter,
+    pub wrap: Wrap,
+
+    // maximum number of trilinear samples taken along the longer axis of a stretched pixel footprint (1 disables it)
+    pub anisotropy: u32,
+}
+
+impl Level
+{
+    // box filters the level down to half its size
+    fn downsampled(&self) -> Self
+    {
+        let width: i32 = i32::max(1, self.width / 2);
+        let height: i32 = i32::max(1, self.height / 2);
+
+        let mut texels: Vec<Color> = Vec::with_capacity((width * height) as usize);
+
+        for y in 0 .. height
+        {
+            for x in 0 .. width
+            {
+                // the edges of odd sized levels are folded into the last texel
+                let x0: i32 = i32::min(x * 2, self.width - 1);
+                let y0: i32 = i32::min(y * 2, self.height - 1);
+                let x1: i32 = i32::min(x * 2 + 1, self.width - 1);
+                let y1: i32 = i32::min(y * 2 + 1, self.height - 1);
+
+                let sum: Color =
+                    self.texels[(self.width * y0 + x0) as usize] + self.texels[(self.width * y0 + x1) as usize] +
+                    self.texels[(self.width * y1 + x0) as usize] + self.texels[(self.width * y1 + x1) as usize];
+
+                texels.push(sum * 0.25);
+            }
+        }
+
+        Self { width, height, texels }
+    }
+}
+
+impl Texture
+{
+    pub fn create(bitmap: &Bitmap) -> Self
+    {
+        // the wrap modes need at least one texel to wrap around
+        assert!(bitmap.width > 0 && bitmap.height > 0, "Error : a texture can't be created from an empty image");
+
+        let mut levels: Vec<Level> = vec!
+        [
+            Level
+            {
+                width: bitmap.width,
+                height: bitmap.height,
+
+                texels: bitmap.pixels.iter().map(|p| Color::unpacked(*p)).collect(),
+            }
+        ];
+
+        // generate the mip chain
+        while let Some(last) = levels.last().filter(|l| l.width > 1 || l.height > 1)
+        {
+            let next: Level = last.downsampled();
+
+            levels.push(next);
+        }
+
+        Self
+        {
+            levels,
+
+            filter: Filter::TRILINEAR,
+            wrap: Wrap::REPEAT,
+
+            anisotropy: 1,
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self>
+    {
+        Ok(Self::create(&Bitmap::load(path)?))
+    }
+
+    pub fn width(&self) -> i32
+    {
+        self.levels[0].width
+    }
+
+    pub fn height(&self) -> i32
+    {
+        self.levels[0].height
+    }
+
+    // maps an integer texel coordinate into [0, size) according to the wrap mode
+    fn wrapped(&self, i: i32, size: i32) -> i32
+    {
+        match self.wrap
+        {
+            Wrap::REPEAT => i.rem_euclid(size),
+            Wrap::CLAMP => i.clamp(0, size - 1),
+            Wrap::MIRROR =>
+            {
+                let period: i32 = i.rem_euclid(size * 2);
+
+                if period < size { period } else { size * 2 - 1 - period }
+            }
+        }
+    }
+
+    fn texel(&self, level: &Level, x: i32, y: i32) -> Color
+    {
+        let x: i32 = self.wrapped(x, level.width);
+        let y: i32 = self.wrapped(y, level.height);
+
+        level.texels[(level.width * y + x) as usize]
+    }
+
+    fn nearest(&self, level: &Level, u: f32, v: f32) -> Color
+    {
+        // texel space, with the origin at the bottom left corner of the image
+        let x: f32 = u * level.width as f32;
+        let y: f32 = (1.0 - v) * level.height as f32;
+
+        self.texel(level, x.floor() as i32, y.floor() as i32)
+    }
+
+    fn bilinear(&self, level: &Level, u: f32, v: f32) -> Color
+    {
+        // blend the four texels whose centers surround the sample point
+        let x: f32 = u * level.width as f32 - 0.5;
+        let y: f32 = (1.0 - v) * level.height as f32 - 0.5;
+
+        let (x0, y0): (i32, i32) = (x.floor() as i32, y.floor() as i32);
+        let (tx, ty): (f32, f32) = (x - x0 as f32, y - y0 as f32);
+
+        let top: Color = self.texel(level, x0, y0) * (1.0 - tx) + self.texel(level, x0 + 1, y0) * tx;
+        let bottom: Color = self.texel(level, x0, y0 + 1) * (1.0 - tx) + self.texel(level, x0 + 1, y0 + 1) * tx;
+
+        top * (1.0 - ty) + bottom * ty
+    }
+
+    fn trilinear(&self, u: f32, v: f32, lod: f32) -> Color
+    {
+        let last: usize = self.levels.len() - 1;
+
+        let lod: f32 = lod.clamp(0.0, last as f32);
+        let level: usize = lod.floor() as usize;
+
+        if level == last { return self.bilinear(&self.levels[last], u, v); }
+
+        let t: f32 = lod - level as f32;
+
+        self.bilinear(&self.levels[level], u, v) * (1.0 - t) + self.bilinear(&self.levels[level + 1], u, v) * t
+    }
+
+    // samples the full size image, without any mipmapping
+    pub fn sample(&self, u: f32, v: f32) -> Color
+    {
+        match self.filter
+        {
+            Filter::NEAREST => self.nearest(&self.levels[0], u, v),
+            Filter::BILINEAR => self.bilinear(&self.levels[0], u, v),
+            Filter::TRILINEAR => self.trilinear(u, v, 0.0),
+        }
+    }
+
+    // samples the texture given the change in UV coordinates to the neighboring pixels along the x & y axis,
+    // which determines the mipmap level & the direction of the anisotropic samples
+    pub fn sample_gradient(&self, u: f32, v: f32, dx: (f32, f32), dy: (f32, f32)) -> Color
+    {
+        if self.filter != Filter::TRILINEAR { return self.sample(u, v); }
+
+        // the pixel footprint in texels
+        let (width, height): (f32, f32) = (self.width() as f32, self.height() as f32);
+
+        let x_length: f32 = f32::hypot(dx.0 * width, dx.1 * height);
+        let y_length: f32 = f32::hypot(dy.0 * width, dy.1 * height);
+
+        // the neighbors of a pixel on the edge of a triangle can be extrapolated behind the view, the footprint is meaningless then
+        if !x_length.is_finite() || !y_length.is_finite() { return self.trilinear(u, v, 0.0); }
+
+        let (major, minor): (f32, f32) = (f32::max(x_length, y_length), f32::min(x_length, y_length));
+
+        // number of samples needed to cover the footprint along its longer axis
+        let count: u32 = if minor > 0.0 { u32::min((major / minor).ceil() as u32, self.anisotropy.max(1)) } else { 1 };
+
+        let lod: f32 = f32::log2(major / count as f32);
+
+        if count == 1 { return self.trilinear(u, v, lod); }
+
+        let axis: (f32, f32) = if x_length >= y_length { dx } else { dy };
+
+        let mut sum: Color = Color::blank();
+
+        // spread the samples evenly along the axis, centered on the pixel
+        for i in 0 .. count
+        {
+            let offset: f32 = (i as f32 + 0.5) / count as f32 - 0.5;
+
+            sum = sum + self.trilinear(u + axis.0 * offset, v + axis.1 * offset, lod);
+        }
+
+        sum / count as f32
+    }
+}
@@ -1,5 +1,5 @@
 use super::color::*;
-use crate::{algebra::{vector3::*, matrix4x4::*}, HEIGHT, WIDTH};
+use crate::algebra::{vector3::*, matrix3x3::*, matrix4x4::*};
 
 #[derive(Clone, Copy)]
 pub struct Vertex
@@ -14,9 +14,11 @@ pub struct Vertex
     pub u: f32,
     pub v: f32,
 
-    pub one: f32,
-
     pub normal: Vector3,
+
+    // directions of increasing u & v along the surface, used to bring normal maps into 3D space
+    pub tangent: Vector3,
+    pub bitangent: Vector3,
 }
 
 impl Vertex
@@ -29,74 +31,27 @@ impl Vertex
             
             color: Color::create(0.0, 0.0, 0.0),
 
-            u: 0.0, v: 0.0, one: 1.0,
+            u: 0.0, v: 0.0,
 
             normal: Vector3::zero(),
+
+            tangent: Vector3::zero(), bitangent: Vector3::zero(),
         }
     }
 
     pub fn create(x: f32, y: f32, z: f32, color: Color, u: f32, v: f32) -> Self
     {
-        Self { x, y, z, w: 1.0, color, u, v, one: 1.0, normal: Vector3::zero() }
+        Self { x, y, z, w: 1.0, color, u, v, normal: Vector3::zero(), tangent: Vector3::zero(), bitangent: Vector3::zero() }
     }
 
     pub fn partial(x: f32, y: f32, z: f32, u: f32, v: f32, normal: Vector3) -> Self
     {
-        Self { x, y, z, w: 1.0, color: Color::blank(), u, v, one: 1.0, normal }
+        Self { x, y, z, w: 1.0, color: Color::blank(), u, v, normal, tangent: Vector3::zero(), bitangent: Vector3::zero() }
     }
 
     pub fn full(x: f32, y: f32, z: f32, color: Color, u: f32, v: f32, normal: Vector3) -> Self
     {
-        Self { x, y, z, w: 1.0, color, u, v, one: 1.0, normal }
-    }
-
-    // transforms the vertex into image space (NDC) using perspective division
-    //
-    //      [-1, 1]-----[1, 1]
-    //      |                |
-    //      |     [0, 0]     |
-    //      |                |
-    //      [-1,-1]-----[1,-1]
-    //
-    pub fn image_space(&self) -> Self
-    {
-        Self
-        { 
-            x: self.x / self.w,
-            y: self.y / self.w,
-            z: self.z / self.w,
-            w: 1.0,
-            
-            color: self.color / self.w,
-            
-            u: self.u / self.w,
-            v: self.v / self.w,
-            
-            one: self.one / self.w,
-            
-            normal: self.normal / self.w,
-        }
-    }
-
-    // transform the vertex into screen space
-    pub fn screen_space(&self) -> Self
-    {
-        Self
-        {
-            x: ((self.x + 1.0) * WIDTH as f32) / 2.0,
-            y: ((1.0 - self.y) * HEIGHT as f32) / 2.0,
-            z: self.z,
-            w: self.w,
-            
-            color: self.color,
-            
-            u: self.u,
-            v: self.v,
-
-            one: self.one,
-
-            normal: self.normal,
-        }
+        Self { x, y, z, w: 1.0, color, u, v, normal, tangent: Vector3::zero(), bitangent: Vector3::zero() }
     }
 
     pub fn transform(&self, matrix: &Matrix4x4) -> Self
@@ -108,41 +63,25 @@ impl Vertex
             z: matrix.get(2, 0) * self.x + matrix.get(2, 1) * self.y + matrix.get(2, 2) * self.z + matrix.get(2, 3) * self.w,
             w: matrix.get(3, 0) * self.x + matrix.get(3, 1) * self.y + matrix.get(3, 2) * self.z + matrix.get(3, 3) * self.w,
 
-            color: self.color, u: self.u, v: self.v, one: self.one, normal: self.normal
+            color: self.color, u: self.u, v: self.v, normal: self.normal, tangent: self.tangent, bitangent: self.bitangent
         }
     }
 
-    // transforms the vertex normal in 3D space
-    pub fn transform_normal(&self, matrix: &Matrix4x4) -> Vector3
+    // transforms the vertex normal in 3D space with a normal matrix (see Mesh::normal_matrix)
+    pub fn transform_normal(&self, matrix: &Matrix3x3) -> Vector3
     {
-        let normal: Vector3 = Vector3::create
-        (
-            matrix.get(0, 0) * self.normal.x + matrix.get(0, 1) * self.normal.y + matrix.get(0, 2) * self.normal.z,
-            matrix.get(1, 0) * self.normal.x + matrix.get(1, 1) * self.normal.y + matrix.get(1, 2) * self.normal.z,
-            matrix.get(2, 0) * self.normal.x + matrix.get(2, 1) * self.normal.y + matrix.get(2, 2) * self.normal.z,
-        );
-
-        normal
+        Vertex::renormalized(*matrix * self.normal)
     }
 
-    // checks if a triangle is out of view
-    pub fn out_of_view(a: &Vertex, b: &Vertex, c: &Vertex) -> bool
-    {  
-        let left: bool = a.x < -a.w && b.x < -b.w && c.x < -c.w;
-        let right: bool = a.x > a.w && b.x > b.w && c.x > c.w;
-
-        let up: bool = a.y > a.w && b.y > b.w && c.y > c.w;
-        let down: bool = a.y < -a.w && b.y < -b.w && c.y < -c.w;
-
-        let far: bool = a.z > a.w && b.z > b.w && c.z > c.w;
-        let close: bool = a.z < 0.0 && b.z < 0.0 && c.z < 0.0;
-
-        left || right || up || down || far || close
-    }   
+    // transforms the tangent & bitangent in 3D space, they follow the surface so the model's upper 3x3 applies
+    pub fn transform_tangents(&self, matrix: &Matrix3x3) -> (Vector3, Vector3)
+    {
+        (Vertex::renormalized(*matrix * self.tangent), Vertex::renormalized(*matrix * self.bitangent))
+    }
 
-    // returns a scalar equal to the signed area of the given triangle (used for backface culling)
-    pub fn signed_triangle_area(a: &Vertex, b: &Vertex, c: &Vertex) -> f32
+    // directions that are left at zero stay that way
+    fn renormalized(direction: Vector3) -> Vector3
     {
-        (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+        if direction.length() > 0.0 { direction.normalized() } else { direction }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,55 @@
+use super::varyings::*;
+
+// signed distances of a clip-space vertex to the six planes of the view frustum, positive means inside
+const PLANES: [fn(&Varyings) -> f32; 6] =
+[
+    |v| v.position.w + v.position.x, // left
+    |v| v.position.w - v.position.x, // right
+    |v| v.position.w + v.position.y, // bottom
+    |v| v.position.w - v.position.y, // top
+    |v| v.position.z,                // near
+    |v| v.position.w - v.position.z, // far
+];
+
+// clips a clip-space triangle against the view frustum (Sutherland–Hodgman), the varyings are interpolated along with the position
+// returns the vertices of the remaining convex polygon in the original winding order, empty if nothing is left
+pub fn clip_triangle(a: &Varyings, b: &Varyings, c: &Varyings) -> Vec<Varyings>
+{
+    let mut polygon: Vec<Varyings> = vec![*a, *b, *c];
+
+    for plane in PLANES
+    {
+        // skip the planes that don't cut the polygon
+        if polygon.iter().all(|v| plane(v) >= 0.0) { continue; }
+
+        let mut clipped: Vec<Varyings> = Vec::with_capacity(polygon.len() + 1);
+
+        for i in 0 .. polygon.len()
+        {
+            let current: &Varyings = &polygon[i];
+            let next: &Varyings = &polygon[(i + 1) % polygon.len()];
+
+            let current_distance: f32 = plane(current);
+            let next_distance: f32 = plane(next);
+
+            if current_distance >= 0.0
+            {
+                clipped.push(*current);
+            }
+
+            // the edge crosses the plane, emit the intersection point
+            if (current_distance >= 0.0) != (next_distance >= 0.0)
+            {
+                let t: f32 = current_distance / (current_distance - next_distance);
+
+                clipped.push(Varyings::interpolate(current, next, t));
+            }
+        }
+
+        polygon = clipped;
+
+        if polygon.len() < 3 { return Vec::new(); }
+    }
+
+    polygon
+}
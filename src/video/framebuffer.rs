@@ -0,0 +1,78 @@
+use super::target::*;
+use crate::{image::bitmap::*, topology::color::*};
+
+// in-memory render target, needs no window or display to work
+pub struct Framebuffer
+{
+    pub width: i32,
+    pub height: i32,
+
+    // packed RGBA pixels (0xRRGGBBAA) in row-major order, starting at the top left corner
+    pub color_buffer: Vec<u32>,
+    pub depth_buffer: Vec<f32>,
+}
+
+impl Framebuffer
+{
+    pub fn create(width: i32, height: i32) -> Self
+    {
+        Self
+        {
+            width,
+            height,
+
+            color_buffer: vec![Color::blank().packed(); (width * height) as usize],
+            depth_buffer: vec![1.0; (width * height) as usize],
+        }
+    }
+
+    pub fn capture_depth(&self) -> Bitmap
+    {
+        Bitmap::grayscale(self.width, self.height, &self.depth_buffer)
+    }
+}
+
+impl Target for Framebuffer
+{
+    fn width(&self) -> i32
+    {
+        self.width
+    }
+
+    fn height(&self) -> i32
+    {
+        self.height
+    }
+
+    fn resize(&mut self, width: i32, height: i32)
+    {
+        *self = Self::create(width, height);
+    }
+
+    fn depth_buffer(&mut self) -> &mut [f32]
+    {
+        &mut self.depth_buffer
+    }
+
+    fn clear(&mut self, color: Color)
+    {
+        self.color_buffer.fill(color.packed());
+    }
+
+    fn draw(&mut self, x: i32, y: i32, color: Color)
+    {
+        self.color_buffer[(self.width * y + x) as usize] = color.packed();
+    }
+
+    fn read(&self, x: i32, y: i32) -> Color
+    {
+        Color::unpacked(self.color_buffer[(self.width * y + x) as usize])
+    }
+
+    fn present(&mut self) { }
+
+    fn capture(&self) -> Bitmap
+    {
+        Bitmap::create(self.width, self.height, self.color_buffer.clone())
+    }
+}
@@ -1,35 +1,154 @@
 use super::view::*;
-use crate::{algebra::vector3::*, topology::{color::*, vertex::*}};
+use crate::{algebra::vector3::*, topology::{color::*, material::*}};
 
-// Blinn-Phong reflection
-pub fn blinn_phong(vt: &mut Vertex, view: &View) -> Color
+#[derive(Clone, Copy)]
+pub enum Source
 {
-    let normal: Vector3 = vt.normal.normalized();
+    // parallel rays traveling along the direction, like the sun
+    DIRECTIONAL { direction: Vector3 },
 
-    let light_direction: Vector3 = Vector3::create(-1.0, -1.0, 1.0).opposite().normalized();
+    // radiates in every direction from a single position
+    POINT { position: Vector3 },
 
-    // Lambert's cosine law
-    let lambertian: f32 = f32::max(0.0, Vector3::dot(&light_direction, &normal));
+    // a cone around the direction, at full strength within the inner angle & fading out towards the outer angle (in degrees)
+    SPOT { position: Vector3, direction: Vector3, inner: f32, outer: f32 },
+}
 
-    // intensity of the specular highlight
-    let specular_exponent: f32 = 48.0;
+#[derive(Clone, Copy)]
+pub struct Light
+{
+    pub source: Source,
+
+    pub color: Color,
+    pub intensity: f32,
+
+    // constant, linear & quadratic falloff over distance (not used by directional lights)
+    pub attenuation: Vector3,
+}
+
+impl Light
+{
+    pub fn directional(direction: &Vector3, color: &Color, intensity: f32) -> Self
+    {
+        Self
+        {
+            source: Source::DIRECTIONAL { direction: direction.normalized() },
+
+            color: *color,
+            intensity,
+
+            attenuation: Vector3::create(1.0, 0.0, 0.0),
+        }
+    }
+
+    pub fn point(position: &Vector3, color: &Color, intensity: f32) -> Self
+    {
+        Self
+        {
+            source: Source::POINT { position: *position },
+
+            color: *color,
+            intensity,
+
+            attenuation: Vector3::create(1.0, 0.09, 0.032),
+        }
+    }
+
+    pub fn spot(position: &Vector3, direction: &Vector3, inner: f32, outer: f32, color: &Color, intensity: f32) -> Self
+    {
+        Self
+        {
+            source: Source::SPOT { position: *position, direction: direction.normalized(), inner, outer },
+
+            color: *color,
+            intensity,
+
+            attenuation: Vector3::create(1.0, 0.09, 0.032),
+        }
+    }
+
+    // returns the direction from the point towards the light & the fraction of the light that reaches the point
+    pub fn incident(&self, point: &Vector3) -> (Vector3, f32)
+    {
+        let falloff = |position: &Vector3| -> (Vector3, f32)
+        {
+            let offset: Vector3 = *position - *point;
+            let distance: f32 = offset.length();
+
+            // a point right on the light has no direction towards it
+            if distance == 0.0 { return (Vector3::zero(), 0.0); }
 
-    let mut specular_highlight: f32 = 0.0;
+            let attenuation: f32 = self.attenuation.x + self.attenuation.y * distance + self.attenuation.z * distance * distance;
 
-    // if there is no diffuse lighting hitting the surface, don't bother calculating the specular
-    if lambertian != 0.0
+            (offset / distance, 1.0 / attenuation)
+        };
+
+        match self.source
+        {
+            Source::DIRECTIONAL { direction } => (direction.opposite(), 1.0),
+
+            Source::POINT { position } => falloff(&position),
+
+            Source::SPOT { position, direction, inner, outer } =>
+            {
+                let (to_light, strength): (Vector3, f32) = falloff(&position);
+
+                // angle between the spot direction & the point, as a cosine
+                let theta: f32 = Vector3::dot(&to_light.opposite(), &direction);
+
+                let inner: f32 = f32::cos(f32::to_radians(inner));
+                let outer: f32 = f32::cos(f32::to_radians(outer));
+
+                let cone: f32 = ((theta - outer) / f32::max(inner - outer, f32::EPSILON)).clamp(0.0, 1.0);
+
+                (to_light, strength * cone)
+            }
+        }
+    }
+}
+
+// what the lighting needs to know about a point on a surface, in world space
+pub struct Surface
+{
+    pub position: Vector3,
+    pub normal: Vector3,
+
+    // the diffuse color, the specular color already includes the material's map
+    pub color: Color,
+    pub specular: Color,
+}
+
+// Blinn-Phong reflection, accumulated over all the lights
+pub fn blinn_phong(surface: &Surface, view: &View, lights: &[Light], ambient: &Color, material: &Material) -> Color
+{
+    let normal: Vector3 = surface.normal.normalized();
+
+    let position: Vector3 = surface.position;
+    let view_direction: Vector3 = (view.position - position).normalized();
+
+    let mut color: Color = surface.color * material.ambient * *ambient + material.emissive;
+
+    for light in lights
     {
-        let view_direction: Vector3 = (view.position - Vector3::create(vt.x, vt.y, vt.z)).normalized();
+        let (light_direction, strength): (Vector3, f32) = light.incident(&position);
+
+        // Lambert's cosine law
+        let lambertian: f32 = f32::max(0.0, Vector3::dot(&light_direction, &normal));
+
+        // if there is no diffuse lighting hitting the surface, don't bother calculating the specular
+        if lambertian == 0.0 || strength <= 0.0 { continue; }
+
 //      let reflect_direction: Vector3 = Vector3::reflect(&light_direction.opposite(), &normal);
         let halfway: Vector3 = (view_direction + light_direction).normalized();
 
-        specular_highlight = f32::powf(f32::max(0.0, Vector3::dot(&halfway, &normal)), specular_exponent);
-//      specular_highlight = f32::powf(f32::max(0.0, Vector3::dot(&view_direction, &reflect_direction)), specular_exponent);
-    }
+        let specular_highlight: f32 = f32::powf(f32::max(0.0, Vector3::dot(&halfway, &normal)), material.shininess);
+//      let specular_highlight: f32 = f32::powf(f32::max(0.0, Vector3::dot(&view_direction, &reflect_direction)), material.shininess);
 
-    let ambient: Color = vt.color * Color::create(0.1, 0.1, 0.1);
-    let diffuse: Color = vt.color * lambertian;
-    let specular: Color = Color::create(1.0, 1.0, 1.0) * specular_highlight;
+        let radiance: Color = light.color * (light.intensity * strength);
+
+        let diffuse: Color = surface.color * radiance * lambertian;
+        color = color + diffuse + radiance * surface.specular * specular_highlight;
+    }
 
-    ambient + diffuse + specular
-}
\ No newline at end of file
+    color
+}
@@ -1,4 +1,10 @@
 pub mod render;
+pub mod clip;
+pub mod target;
+pub mod framebuffer;
+#[cfg(feature = "sdl")]
 pub mod screen;
 pub mod view;
-pub mod light;
\ No newline at end of file
+pub mod light;
+pub mod shader;
+pub mod varyings;
@@ -1,120 +1,226 @@
-use super::{screen::*, view::*, light::*};
-use crate::{algebra::matrix4x4::*, topology::{mesh::*, part::*, color::*, vertex::*}};
+use super::{target::*, view::*, light::*, clip::*, shader::*, varyings::*};
+use crate::{algebra::{vector3::*, vector4::*}, topology::{mesh::*, part::*, color::*, material::*}};
 
-use sdl2::rect::Point;
+use std::rc::Rc;
 
-pub struct Render
+pub struct Render<T: Target>
 {
-    pub screen: Screen,
+    pub target: T,
     pub view: View,
+
+    // the lights shining on every mesh & the light reaching all surfaces regardless of their orientation
+    pub lights: Vec<Light>,
+    pub ambient: Color,
+
+    // used by the meshes & materials that don't have a shader of their own
+    pub shader: Rc<dyn Shader>,
+
+    // the shaders the meshes & materials can pick by id
+    pub shaders: Vec<Rc<dyn Shader>>,
+
+    // the size of the target the view's aspect ratio was last matched to
+    viewport: (i32, i32),
 }
 
-impl Render
+impl<T: Target> Render<T>
 {
-    pub fn create(screen: Screen, view: View) -> Self
+    pub fn create(target: T, view: View) -> Self
     {
-        Self { screen, view }
+        // a single white sun by default
+        let lights: Vec<Light> = vec![Light::directional(&Vector3::create(-1.0, -1.0, 1.0), &Color::create(1.0, 1.0, 1.0), 1.0)];
+
+        let mut render = Self { target, view, lights, ambient: Color::create(0.1, 0.1, 0.1), shader: Rc::new(Phong), shaders: Vec::new(), viewport: (0, 0) };
+
+        render.fit_view();
+
+        render
+    }
+
+    // matches the view's aspect ratio to the target when the target was resized, so an aspect ratio set by hand stays until then
+    // a view that still follows the target, like a new one put in place of the old, is matched even without a resize
+    fn fit_view(&mut self)
+    {
+        let viewport: (i32, i32) = (self.target.width(), self.target.height());
+
+        // a minimized window has no size to match
+        if viewport.0 <= 0 || viewport.1 <= 0 { return; }
+
+        if viewport == self.viewport && !self.view.fitted() { return; }
+
+        self.viewport = viewport;
+        self.view.fit_aspect(viewport.0 as f32 / viewport.1 as f32);
+    }
+
+    // makes a shader available to the meshes & materials, returns the id they refer to it with
+    pub fn add_shader(&mut self, shader: Rc<dyn Shader>) -> usize
+    {
+        self.shaders.push(shader);
+
+        self.shaders.len() - 1
     }
 
     pub fn update(&mut self)
     {
-        self.screen.canvas.present();
+        self.target.present();
     }
     
-    pub fn clear(&mut self, color: sdl2::pixels::Color)
+    pub fn clear(&mut self, color: Color)
     {
-        for i in self.screen.depth_buffer.iter_mut()
+        for i in self.target.depth_buffer().iter_mut()
         {
             *i = 1.0;
         }
         
-        self.screen.canvas.set_draw_color(color);
-        self.screen.canvas.clear();
+        self.target.clear(color);
     }
 
     pub fn pixel(&mut self, x: i32, y: i32, z: f32, color: Color)
     {
-        if x >= 0 && x < self.screen.width && y >= 0 && y < self.screen.height
+        let (width, height) = (self.target.width(), self.target.height());
+
+        if x >= 0 && x < width && y >= 0 && y < height
         {
+            let depth_buffer = self.target.depth_buffer();
+
             // perform a depth test
-            if z < self.screen.depth_buffer[(self.screen.width * y + x) as usize]
+            if z < depth_buffer[(width * y + x) as usize]
             {
-                self.screen.depth_buffer[(self.screen.width * y + x) as usize] = z;
-                self.screen.canvas.set_draw_color(sdl2::pixels::Color::RGB((color.r * 255.0) as u8, (color.g * 255.0) as u8, (color.b * 255.0) as u8));
-                self.screen.canvas.draw_point(Point::new(x, y)).unwrap();
+                depth_buffer[(width * y + x) as usize] = z;
+                self.target.draw(x, y, color);
             }
         }
     }
 
-    pub fn triangle(&mut self, a: &Vertex, b: &Vertex, c: &Vertex)
+    // blends a translucent pixel over the color buffer, it is depth tested but doesn't occlude anything
+    pub fn blend(&mut self, x: i32, y: i32, z: f32, color: Color, opacity: f32)
+    {
+        let (width, height) = (self.target.width(), self.target.height());
+
+        if x >= 0 && x < width && y >= 0 && y < height && z < self.target.depth_buffer()[(width * y + x) as usize]
+        {
+            let background: Color = self.target.read(x, y);
+
+            self.target.draw(x, y, color * opacity + background * (1.0 - opacity));
+        }
+    }
+
+    pub fn triangle<V>(&mut self, a: &Varyings, b: &Varyings, c: &Varyings, shader: &dyn Shader<V>, uniforms: &Uniforms)
     {
         // signed area of the triangle
-        let area: f32 = Vertex::signed_triangle_area(a, b, c);
+        let area: f32 = Varyings::signed_triangle_area(a, b, c);
 
         // perform backface culling
         if area > 0.0 { return; }
 
+        let (pa, pb, pc) = (&a.position, &b.position, &c.position);
+
         // bounding box of the triangle
-        let xmin: i32 = f32::min(f32::min(a.x, b.x), c.x).floor() as i32;
-        let xmax: i32 = f32::max(f32::max(a.x, b.x), c.x).floor() as i32;
-        let ymin: i32 = f32::min(f32::min(a.y, b.y), c.y).ceil() as i32;
-        let ymax: i32 = f32::max(f32::max(a.y, b.y), c.y).ceil() as i32;
+        let xmin: i32 = f32::min(f32::min(pa.x, pb.x), pc.x).floor() as i32;
+        let xmax: i32 = f32::max(f32::max(pa.x, pb.x), pc.x).floor() as i32;
+        let ymin: i32 = f32::min(f32::min(pa.y, pb.y), pc.y).ceil() as i32;
+        let ymax: i32 = f32::max(f32::max(pa.y, pb.y), pc.y).ceil() as i32;
 
         let reciprocal_area = 1.0 / area;
 
-        for y in ymin ..= ymax
+        // barycentric coordinates of a pixel
+        let barycentric = |x: i32, y: i32| -> (f32, f32, f32)
+        {
+            let alpha: f32 = ((pb.y - pc.y) * (x as f32 - pc.x) + (pc.x - pb.x) * (y as f32 - pc.y)) * reciprocal_area;
+            let beta: f32 = ((pc.y - pa.y) * (x as f32 - pc.x) + (pa.x - pc.x) * (y as f32 - pc.y)) * reciprocal_area;
+
+            (alpha, beta, 1.0 - alpha - beta)
+        };
+
+        // pixels are shaded in 2x2 quads, so that the change in the varyings can be read from the neighboring pixels
+        for qy in (ymin & !1 ..= ymax).step_by(2)
         {
-            for x in xmin ..= xmax
+            for qx in (xmin & !1 ..= xmax).step_by(2)
             {
-                // barycentric coordinates
-                let alpha: f32 = ((b.y - c.y) * (x as f32 - c.x) + (c.x - b.x) * (y as f32 - c.y)) * reciprocal_area;
-                let beta: f32 = ((c.y - a.y) * (x as f32 - c.x) + (a.x - c.x) * (y as f32 - c.y)) * reciprocal_area;
-                let gamma: f32 = 1.0 - alpha - beta;
+                let quad: [(i32, i32); 4] = [(qx, qy), (qx + 1, qy), (qx, qy + 1), (qx + 1, qy + 1)];
+                let weights: [(f32, f32, f32); 4] = quad.map(|(x, y)| barycentric(x, y));
+
+                if weights.iter().all(|(alpha, beta, gamma)| *alpha < 0.0 || *beta < 0.0 || *gamma < 0.0) { continue; }
+
+                // perspective-correct varyings of the whole quad, including the pixels outside of the triangle
+                let fragments: [Varyings; 4] = weights.map(|weights| Varyings::barycentric(a, b, c, weights).perspective_corrected());
+
+                // derivatives along the x & y axis, shared by the whole quad
+                let derivatives: Derivatives = Derivatives { dx: fragments[1] - fragments[0], dy: fragments[2] - fragments[0] };
 
-                if alpha >= 0.0 && beta >= 0.0 && gamma >= 0.0
+                for ((x, y), ((alpha, beta, gamma), fragment)) in quad.into_iter().zip(weights.into_iter().zip(fragments))
                 {
-                    let mut frag: Vertex = Vertex::blank();
-
-                    // depth interpolation
-                    frag.z = a.z * alpha + b.z * beta + c.z * gamma;
-                    // color interpolation
-                    frag.color = a.color * alpha + b.color * beta + c.color * gamma;
-                    // reciprocal interpolation
-                    frag.one = a.one * alpha + b.one * beta + c.one * gamma;
-                    // uv coordinates interpolation
-                    frag.u = a.u * alpha + b.u * beta + c.u * gamma;
-                    frag.v = a.v * alpha + b.v * beta + c.v * gamma;
-                    // normal interpolation
-                    frag.normal = a.normal * alpha + b.normal * beta + c.normal * gamma;
-
-                    // perspective-correct interpolation
-                    frag.color = frag.color / frag.one;
-                    frag.u = frag.u / frag.one;
-                    frag.v = frag.v / frag.one;
-                    
-                    self.pixel(x, y, frag.z, blinn_phong(&mut frag, &self.view));
+                    if alpha >= 0.0 && beta >= 0.0 && gamma >= 0.0
+                    {
+                        let Some(color) = shader.fragment(&fragment, &derivatives, uniforms) else { continue; };
+
+                        // the depth is interpolated linearly on the screen
+                        let z: f32 = fragment.position.z;
+
+                        if uniforms.material.opacity < 1.0
+                        {
+                            self.blend(x, y, z, color, uniforms.material.opacity);
+                        }
+                        else
+                        {
+                            self.pixel(x, y, z, color);
+                        }
+                    }
                 }
             }
         }
     }
 
     pub fn process(&mut self, mesh: &mut Mesh)
+    {
+        let shaders: Vec<Rc<dyn Shader>> = mesh.parts.iter()
+            .map(|part| part.material.shader.or(mesh.shader).map_or_else(|| self.shader.clone(), |id|
+            {
+                self.shaders.get(id).cloned().unwrap_or_else(|| panic!("Error : unknown shader id {}, it wasn't returned by add_shader", id))
+            }))
+            .collect();
+
+        self.draw(mesh, &shaders);
+    }
+
+    // draws a mesh of another vertex format than the built-in one, with the shader that reads it
+    pub fn process_with<V>(&mut self, mesh: &mut Mesh<V>, shader: Rc<dyn Shader<V>>)
+    {
+        let shaders: Vec<Rc<dyn Shader<V>>> = vec![shader; mesh.parts.len()];
+
+        self.draw(mesh, &shaders);
+    }
+
+    // draws the parts of a mesh, each with its own shader
+    fn draw<V>(&mut self, mesh: &mut Mesh<V>, shaders: &[Rc<dyn Shader<V>>])
     {
         mesh.update();
-        
-        let transformation_matrix: Matrix4x4 = self.view.perspective() * self.view.view() * mesh.model;
 
-        let mut vertices: Vec<Vertex> = mesh.vertices.clone();
+        // the target may have been resized since the last mesh
+        self.fit_view();
+
+        let (width, height) = (self.target.width(), self.target.height());
 
-        // transform the vertices & their normals in 3D space
-        for vertex in &mut vertices
+        let mut uniforms: Uniforms = Uniforms
         {
-            *vertex = vertex.transform(&transformation_matrix).image_space().screen_space();
-            vertex.normal = vertex.transform_normal(&mesh.model);
-        }
+            model: mesh.model,
+            normal_matrix: mesh.normal_matrix,
+            view_projection: self.view.projection() * self.view.view(),
+
+            view: self.view.clone(),
+            lights: self.lights.clone(),
+            ambient: self.ambient,
 
-        for part in &mesh.parts
+            material: Rc::new(Material::create()),
+        };
+
+        // the vertices the current part's vertex stage already went through, a part only pays for the vertices it uses
+        let mut vertices: Vec<Varyings> = vec![Varyings::create(Vector4::zero()); mesh.vertices.len()];
+        let mut stamps: Vec<usize> = vec![0; vertices.len()];
+
+        for ((stamp, part), shader) in (1 ..).zip(&mesh.parts).zip(shaders)
         {
+            uniforms.material = part.material.clone();
+
             let mut start: usize = part.index;
             
             match part.topology
@@ -123,25 +229,39 @@ impl Render
                 {
                     for _ in 0 .. part.count
                     {
-                        let mut a = mesh.vertices[mesh.indices[start]].transform(&transformation_matrix);
-                        let mut b = mesh.vertices[mesh.indices[start + 1]].transform(&transformation_matrix);
-                        let mut c = mesh.vertices[mesh.indices[start + 2]].transform(&transformation_matrix);
-
-                        // ensure that only the triangles that are within the view space are drawn
-                        if !Vertex::out_of_view(&a, &b, &c)
+                        // run the vertex stage, into clip space
+                        let [a, b, c] = [0, 1, 2].map(|k|
                         {
-                            a = vertices[mesh.indices[start]];
-                            b = vertices[mesh.indices[start + 1]];
-                            c = vertices[mesh.indices[start + 2]];
+                            let index: usize = mesh.indices[start + k];
 
-                            // all vertices are now ready to be rendered
-                            self.triangle(&a, &b, &c);
-                        }
+                            if stamps[index] != stamp
+                            {
+                                stamps[index] = stamp;
+                                vertices[index] = shader.vertex(&mesh.vertices[index], &uniforms);
+                            }
+
+                            vertices[index]
+                        });
 
                         start += 3;
+
+                        // skip the triangles that are entirely out of view
+                        if Varyings::out_of_view(&a, &b, &c) { continue; }
+
+                        // cut the triangles that cross the frustum planes before the perspective division
+                        let polygon: Vec<Varyings> = clip_triangle(&a, &b, &c)
+                            .iter()
+                            .map(|vertex| vertex.image_space().screen_space(width, height))
+                            .collect();
+
+                        // all vertices are now ready to be rendered, the clipped polygon is convex so a fan covers it
+                        for i in 1 .. polygon.len().saturating_sub(1)
+                        {
+                            self.triangle(&polygon[0], &polygon[i], &polygon[i + 1], shader.as_ref(), &uniforms);
+                        }
                     }
                 }
             }
         }
     }
-}
\ No newline at end of file
+}
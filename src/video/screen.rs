@@ -1,8 +1,9 @@
-use super::view::*;
+use super::{framebuffer::*, target::*, view::*};
+use crate::{image::bitmap::*, topology::color::*};
 
 use std::time::{Instant, Duration};
 
-use sdl2::{video::Window, render::Canvas, event::Event, keyboard::{Keycode, Scancode}};
+use sdl2::{video::Window, render::{Canvas, Texture}, pixels::PixelFormatEnum, event::{Event, WindowEvent}, keyboard::{Keycode, Scancode}};
 
 const SPEED: f32 = 2.0;
 
@@ -11,13 +12,12 @@ const TARGET_TICK: u32 = 1000 / FPS;
 
 pub struct Screen
 {
-    pub width: i32,
-    pub height: i32,
-
     pub sdl: sdl2::Sdl,
     pub canvas: Canvas<Window>,
-    
-    pub depth_buffer: Vec<f32>,
+
+    // the frame is drawn in memory & uploaded to the window in one go when presented
+    pub framebuffer: Framebuffer,
+    texture: Texture,
 
     previous_tick: Instant,
     pub delta_time: f32,
@@ -30,29 +30,31 @@ impl Screen
         let sdl = sdl2::init().unwrap();
         let video = sdl.video().unwrap();
 
-        let window = video.window("gl_rust", width as u32, height as u32).build().unwrap();
+        let window = video.window("gl_rust", width as u32, height as u32).resizable().build().unwrap();
         let canvas = window.into_canvas().build().unwrap();
 
-        let mut depth_buffer = Vec::new();
+        let texture = Self::streaming_texture(&canvas, width, height);
 
-        depth_buffer.resize((width * height) as usize, 1.0);
-        
         Self
         {
-            width,
-            height,
-            
             sdl,
             canvas,
 
-            depth_buffer,
+            framebuffer: Framebuffer::create(width, height),
+            texture,
 
             previous_tick: Instant::now(),
             delta_time: 0.0,
         }
     }
 
-    pub fn input(&self, view: &mut View)
+    // a texture the color buffer can be copied into every frame, RGBA8888 pixels are native-endian 0xRRGGBBAA values like the buffer's
+    fn streaming_texture(canvas: &Canvas<Window>, width: i32, height: i32) -> Texture
+    {
+        canvas.texture_creator().create_texture_streaming(PixelFormatEnum::RGBA8888, width as u32, height as u32).unwrap()
+    }
+
+    pub fn input(&mut self, view: &mut View)
     {
         let mut events = self.sdl.event_pump().unwrap();
 
@@ -76,6 +78,8 @@ impl Screen
             {
                 Event::KeyDown { keycode: Some(Keycode::Escape), .. } => std::process::exit(0),
 
+                Event::Window { win_event: WindowEvent::SizeChanged(width, height), .. } => self.resize(width, height),
+
                 Event::Quit { .. } => std::process::exit(0),
 
                 _ => { }
@@ -97,4 +101,75 @@ impl Screen
 
         self.previous_tick = now;
     }
-}
\ No newline at end of file
+}
+
+impl Target for Screen
+{
+    fn width(&self) -> i32
+    {
+        self.framebuffer.width()
+    }
+
+    fn height(&self) -> i32
+    {
+        self.framebuffer.height()
+    }
+
+    fn resize(&mut self, width: i32, height: i32)
+    {
+        // a window collapsed to nothing keeps its buffers until it comes back
+        if width <= 0 || height <= 0 { return; }
+
+        let texture: Texture = Self::streaming_texture(&self.canvas, width, height);
+
+        // SAFETY: the old texture belongs to this canvas, which is still alive, & nothing refers to it anymore
+        unsafe { std::mem::replace(&mut self.texture, texture).destroy(); }
+
+        self.framebuffer.resize(width, height);
+    }
+
+    fn depth_buffer(&mut self) -> &mut [f32]
+    {
+        self.framebuffer.depth_buffer()
+    }
+
+    fn clear(&mut self, color: Color)
+    {
+        self.framebuffer.clear(color);
+    }
+
+    fn draw(&mut self, x: i32, y: i32, color: Color)
+    {
+        self.framebuffer.draw(x, y, color);
+    }
+
+    fn read(&self, x: i32, y: i32) -> Color
+    {
+        self.framebuffer.read(x, y)
+    }
+
+    fn present(&mut self)
+    {
+        let (buffer, width) = (&self.framebuffer.color_buffer, self.framebuffer.width as usize);
+
+        self.texture.with_lock(None, |pixels: &mut [u8], pitch: usize|
+        {
+            // the rows of the texture may be padded
+            for (row, colors) in pixels.chunks_exact_mut(pitch).zip(buffer.chunks_exact(width))
+            {
+                for (pixel, color) in row.chunks_exact_mut(4).zip(colors)
+                {
+                    pixel.copy_from_slice(&color.to_ne_bytes());
+                }
+            }
+        }).unwrap();
+
+        self.canvas.copy(&self.texture, None, None).unwrap();
+        self.canvas.present();
+    }
+
+    fn capture(&self) -> Bitmap
+    {
+        self.framebuffer.capture()
+    }
+}
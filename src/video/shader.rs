@@ -0,0 +1,227 @@
+use super::{light::*, view::*, varyings::*};
+use crate::{algebra::{matrix3x3::*, matrix4x4::*, vector3::*, vector4::*}, topology::{color::*, material::*, texture::*, vertex::*}};
+
+use std::rc::Rc;
+
+// everything a shader can read that is the same for a whole part of a mesh
+pub struct Uniforms
+{
+    // object to world space, & the matching normal matrix
+    pub model: Matrix4x4,
+    pub normal_matrix: Matrix3x3,
+
+    // world to clip space
+    pub view_projection: Matrix4x4,
+
+    pub view: View,
+    pub lights: Vec<Light>,
+    pub ambient: Color,
+
+    pub material: Rc<Material>,
+}
+
+// change of every varying to the next pixel on the right & below, used to filter textures
+#[derive(Clone, Copy)]
+pub struct Derivatives
+{
+    pub dx: Varyings,
+    pub dy: Varyings,
+}
+
+impl Derivatives
+{
+    // the derivatives of a pair of slots, in the form the textures take them
+    pub fn of(&self, slot: usize) -> ((f32, f32), (f32, f32))
+    {
+        ((self.dx.get(slot), self.dx.get(slot + 1)), (self.dy.get(slot), self.dy.get(slot + 1)))
+    }
+}
+
+// where the built-in shaders keep their varyings, custom shaders are free to use the slots from CUSTOM on
+pub const COLOR: usize = 0;
+pub const UV: usize = 3;
+pub const WORLD: usize = 5;
+pub const NORMAL: usize = 8;
+pub const TANGENT: usize = 11;
+pub const BITANGENT: usize = 14;
+pub const CUSTOM: usize = 17;
+
+// the programmable stages of the pipeline, set on a material or a mesh (the material's wins) to replace the default one
+// the vertex stage reads the mesh's own vertex format, the rest of the pipeline only ever sees the varyings
+pub trait Shader<V = Vertex>
+{
+    // object space vertex to clip space position, with the varyings to interpolate for the fragment stage
+    fn vertex(&self, vertex: &V, uniforms: &Uniforms) -> Varyings;
+
+    // color of a fragment from its interpolated varyings, none discards it
+    fn fragment(&self, fragment: &Varyings, derivatives: &Derivatives, uniforms: &Uniforms) -> Option<Color>;
+}
+
+// the usual vertex stage: clip space position, with the color, uvs, & the position, normal & tangents in world space
+pub fn transform(vertex: &Vertex, uniforms: &Uniforms) -> Varyings
+{
+    let position: Vector4 = Vector4::create(vertex.x, vertex.y, vertex.z, vertex.w);
+    let world: Vector4 = uniforms.model * position;
+
+    let (tangent, bitangent) = vertex.transform_tangents(&uniforms.model.upper());
+
+    let mut varyings: Varyings = Varyings::create(uniforms.view_projection * world);
+
+    varyings.set_color(COLOR, &vertex.color);
+    varyings.set(UV, vertex.u);
+    varyings.set(UV + 1, vertex.v);
+    varyings.set_vector3(WORLD, &world.xyz());
+    varyings.set_vector3(NORMAL, &vertex.transform_normal(&uniforms.normal_matrix));
+    varyings.set_vector3(TANGENT, &tangent);
+    varyings.set_vector3(BITANGENT, &bitangent);
+
+    varyings
+}
+
+// samples a texture at the fragment's uvs
+pub fn sample(texture: &Texture, fragment: &Varyings, derivatives: &Derivatives) -> Color
+{
+    let (dx, dy) = derivatives.of(UV);
+
+    texture.sample_gradient(fragment.get(UV), fragment.get(UV + 1), dx, dy)
+}
+
+// surface color: the vertex color, modulated by the material & its diffuse map
+pub fn albedo(fragment: &Varyings, derivatives: &Derivatives, material: &Material) -> Color
+{
+    let color: Color = fragment.color(COLOR) * material.diffuse;
+
+    match &material.diffuse_map
+    {
+        Some(texture) => color * sample(texture, fragment, derivatives),
+        None => color,
+    }
+}
+
+// the interpolated normal, perturbed by the material's normal map
+// the normal map is in tangent space, its channels are remapped from [0, 1] to [-1, 1]
+pub fn surface_normal(fragment: &Varyings, derivatives: &Derivatives, material: &Material) -> Vector3
+{
+    let normal: Vector3 = fragment.vector3(NORMAL);
+    let tangent: Vector3 = fragment.vector3(TANGENT);
+
+    match material.normal_map.as_ref().filter(|_| tangent.length() > 0.0)
+    {
+        Some(texture) =>
+        {
+            let texel: Color = sample(texture, fragment, derivatives);
+
+            tangent.normalized() * (texel.r * 2.0 - 1.0) +
+            fragment.vector3(BITANGENT).normalized() * (texel.g * 2.0 - 1.0) +
+            normal.normalized() * (texel.b * 2.0 - 1.0)
+        }
+
+        None => normal,
+    }
+}
+
+// Blinn-Phong lighting of the textured surface, the default
+pub struct Phong;
+
+impl Shader for Phong
+{
+    fn vertex(&self, vertex: &Vertex, uniforms: &Uniforms) -> Varyings
+    {
+        transform(vertex, uniforms)
+    }
+
+    fn fragment(&self, fragment: &Varyings, derivatives: &Derivatives, uniforms: &Uniforms) -> Option<Color>
+    {
+        let material: &Material = &uniforms.material;
+
+        let specular: Color = match &material.specular_map
+        {
+            Some(texture) => material.specular * sample(texture, fragment, derivatives),
+            None => material.specular,
+        };
+
+        let surface: Surface = Surface
+        {
+            position: fragment.vector3(WORLD),
+            normal: surface_normal(fragment, derivatives, material),
+            color: albedo(fragment, derivatives, material),
+            specular,
+        };
+
+        Some(blinn_phong(&surface, &uniforms.view, &uniforms.lights, &uniforms.ambient, material))
+    }
+}
+
+// the surface color as is, no lighting
+pub struct Unlit;
+
+impl Shader for Unlit
+{
+    fn vertex(&self, vertex: &Vertex, uniforms: &Uniforms) -> Varyings
+    {
+        transform(vertex, uniforms)
+    }
+
+    fn fragment(&self, fragment: &Varyings, derivatives: &Derivatives, uniforms: &Uniforms) -> Option<Color>
+    {
+        Some(albedo(fragment, derivatives, &uniforms.material) + uniforms.material.emissive)
+    }
+}
+
+// cel shading, the diffuse lighting is cut into flat bands
+pub struct Toon
+{
+    pub bands: u32,
+}
+
+impl Shader for Toon
+{
+    fn vertex(&self, vertex: &Vertex, uniforms: &Uniforms) -> Varyings
+    {
+        transform(vertex, uniforms)
+    }
+
+    fn fragment(&self, fragment: &Varyings, derivatives: &Derivatives, uniforms: &Uniforms) -> Option<Color>
+    {
+        let material: &Material = &uniforms.material;
+
+        let color: Color = albedo(fragment, derivatives, material);
+        let normal: Vector3 = surface_normal(fragment, derivatives, material).normalized();
+
+        let bands: f32 = self.bands.max(1) as f32;
+
+        let mut result: Color = color * material.ambient * uniforms.ambient + material.emissive;
+
+        for light in &uniforms.lights
+        {
+            let (direction, strength) = light.incident(&fragment.vector3(WORLD));
+
+            let lambertian: f32 = f32::max(0.0, Vector3::dot(&direction, &normal)) * strength;
+
+            // rounded up, so only the surfaces facing away stay in the dark
+            let band: f32 = f32::ceil(lambertian * bands) / bands;
+
+            result = result + color * light.color * (light.intensity * band);
+        }
+
+        Some(result)
+    }
+}
+
+// shows the world space normals as colors, each axis from -1 to 1 mapped to a channel from 0 to 1
+pub struct Normals;
+
+impl Shader for Normals
+{
+    fn vertex(&self, vertex: &Vertex, uniforms: &Uniforms) -> Varyings
+    {
+        transform(vertex, uniforms)
+    }
+
+    fn fragment(&self, fragment: &Varyings, derivatives: &Derivatives, uniforms: &Uniforms) -> Option<Color>
+    {
+        let normal: Vector3 = surface_normal(fragment, derivatives, &uniforms.material).normalized();
+
+        Some(Color::create(normal.x * 0.5 + 0.5, normal.y * 0.5 + 0.5, normal.z * 0.5 + 0.5))
+    }
+}
@@ -0,0 +1,28 @@
+use crate::{image::bitmap::*, topology::color::*};
+
+// a surface the rasterizer can draw into (a window, an offscreen buffer, ...)
+pub trait Target
+{
+    fn width(&self) -> i32;
+    fn height(&self) -> i32;
+
+    // reallocates the color & depth buffers for a new size, their contents are lost
+    fn resize(&mut self, width: i32, height: i32);
+
+    fn depth_buffer(&mut self) -> &mut [f32];
+
+    // fills the whole color buffer with a single color
+    fn clear(&mut self, color: Color);
+
+    // writes a single pixel, the coordinates are assumed to be within bounds
+    fn draw(&mut self, x: i32, y: i32, color: Color);
+
+    // reads back a single pixel, used for blending
+    fn read(&self, x: i32, y: i32) -> Color;
+
+    // makes the finished frame visible
+    fn present(&mut self);
+
+    // copies the current contents of the color buffer into an image
+    fn capture(&self) -> Bitmap;
+}
@@ -0,0 +1,184 @@
+use crate::{algebra::{vector2::*, vector3::*, vector4::*}, topology::color::*};
+
+// number of floats a shader can pass from its vertex stage to its fragment stage
+pub const CAPACITY: usize = 32;
+
+// the output of a vertex stage: a clip-space position & the values to interpolate across the triangle
+// the clipper, the perspective division & the rasterizer treat every slot the same, whatever the shader stores in it
+#[derive(Clone, Copy)]
+pub struct Varyings
+{
+    // clip space, then x & y in pixels, z in [0, 1] & w holding 1 / w once in screen space
+    pub position: Vector4,
+
+    pub data: [f32; CAPACITY],
+}
+
+impl Varyings
+{
+    pub fn create(position: Vector4) -> Self
+    {
+        Self { position, data: [0.0; CAPACITY] }
+    }
+
+    pub fn get(&self, slot: usize) -> f32
+    {
+        self.data[slot]
+    }
+
+    pub fn set(&mut self, slot: usize, value: f32)
+    {
+        self.data[slot] = value;
+    }
+
+    // the vectors & colors take up consecutive slots, starting at the given one
+    pub fn vector2(&self, slot: usize) -> Vector2
+    {
+        Vector2::create(self.data[slot], self.data[slot + 1])
+    }
+
+    pub fn set_vector2(&mut self, slot: usize, value: &Vector2)
+    {
+        self.data[slot .. slot + 2].copy_from_slice(&[value.x, value.y]);
+    }
+
+    pub fn vector3(&self, slot: usize) -> Vector3
+    {
+        Vector3::create(self.data[slot], self.data[slot + 1], self.data[slot + 2])
+    }
+
+    pub fn set_vector3(&mut self, slot: usize, value: &Vector3)
+    {
+        self.data[slot .. slot + 3].copy_from_slice(&[value.x, value.y, value.z]);
+    }
+
+    pub fn color(&self, slot: usize) -> Color
+    {
+        Color::create(self.data[slot], self.data[slot + 1], self.data[slot + 2])
+    }
+
+    pub fn set_color(&mut self, slot: usize, value: &Color)
+    {
+        self.data[slot .. slot + 3].copy_from_slice(&[value.r, value.g, value.b]);
+    }
+
+    // transforms the position into image space (NDC) using perspective division
+    //
+    //      [-1, 1]-----[1, 1]
+    //      |                |
+    //      |     [0, 0]     |
+    //      |                |
+    //      [-1,-1]-----[1,-1]
+    //
+    // the varyings are divided as well so they can be interpolated linearly on the screen, w keeps 1 / w to undo it
+    pub fn image_space(&self) -> Self
+    {
+        let one: f32 = 1.0 / self.position.w;
+
+        Self
+        {
+            position: Vector4::create(self.position.x * one, self.position.y * one, self.position.z * one, one),
+            data: self.data.map(|value| value * one),
+        }
+    }
+
+    // transform the position into screen space of the given size
+    pub fn screen_space(&self, width: i32, height: i32) -> Self
+    {
+        let mut result: Varyings = *self;
+
+        result.position.x = ((self.position.x + 1.0) * width as f32) / 2.0;
+        result.position.y = ((1.0 - self.position.y) * height as f32) / 2.0;
+
+        result
+    }
+
+    // undoes the division by w of varyings interpolated on the screen (perspective-correct interpolation)
+    pub fn perspective_corrected(&self) -> Self
+    {
+        let w: f32 = 1.0 / self.position.w;
+
+        Self { position: self.position, data: self.data.map(|value| value * w) }
+    }
+
+    // linear interpolation of the position & every slot between two vertices
+    pub fn interpolate(a: &Varyings, b: &Varyings, t: f32) -> Self
+    {
+        *a * (1.0 - t) + *b * t
+    }
+
+    // weighted sum of the three vertices of a triangle, with barycentric coordinates
+    pub fn barycentric(a: &Varyings, b: &Varyings, c: &Varyings, (alpha, beta, gamma): (f32, f32, f32)) -> Self
+    {
+        let mut result: Varyings = Varyings::create(a.position * alpha + b.position * beta + c.position * gamma);
+
+        for (i, value) in result.data.iter_mut().enumerate()
+        {
+            *value = a.data[i] * alpha + b.data[i] * beta + c.data[i] * gamma;
+        }
+
+        result
+    }
+
+    // checks if a clip-space triangle is out of view
+    pub fn out_of_view(a: &Varyings, b: &Varyings, c: &Varyings) -> bool
+    {
+        let (a, b, c) = (&a.position, &b.position, &c.position);
+
+        let left: bool = a.x < -a.w && b.x < -b.w && c.x < -c.w;
+        let right: bool = a.x > a.w && b.x > b.w && c.x > c.w;
+
+        let up: bool = a.y > a.w && b.y > b.w && c.y > c.w;
+        let down: bool = a.y < -a.w && b.y < -b.w && c.y < -c.w;
+
+        let far: bool = a.z > a.w && b.z > b.w && c.z > c.w;
+        let close: bool = a.z < 0.0 && b.z < 0.0 && c.z < 0.0;
+
+        left || right || up || down || far || close
+    }
+
+    // returns a scalar equal to the signed area of the given screen-space triangle (used for backface culling)
+    pub fn signed_triangle_area(a: &Varyings, b: &Varyings, c: &Varyings) -> f32
+    {
+        let (a, b, c) = (&a.position, &b.position, &c.position);
+
+        (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+    }
+}
+
+impl std::ops::Add<Varyings> for Varyings
+{
+    type Output = Varyings;
+
+    fn add(self, scalar: Varyings) -> Varyings
+    {
+        let mut result: Varyings = Varyings::create(self.position + scalar.position);
+
+        for (value, (a, b)) in result.data.iter_mut().zip(self.data.iter().zip(scalar.data))
+        {
+            *value = a + b;
+        }
+
+        result
+    }
+}
+
+impl std::ops::Sub<Varyings> for Varyings
+{
+    type Output = Varyings;
+
+    fn sub(self, scalar: Varyings) -> Varyings
+    {
+        self + scalar * -1.0
+    }
+}
+
+impl std::ops::Mul<f32> for Varyings
+{
+    type Output = Varyings;
+
+    fn mul(self, scalar: f32) -> Varyings
+    {
+        Varyings { position: self.position * scalar, data: self.data.map(|value| value * scalar) }
+    }
+}
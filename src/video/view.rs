@@ -1,5 +1,15 @@
-use crate::{algebra::{matrix4x4::*, vector3::*}, WIDTH, HEIGHT};
+use crate::algebra::{matrix4x4::*, vector3::*};
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum Projection
+{
+    PERSPECTIVE,
+
+    // the height of the view volume in world units, the width follows the aspect ratio
+    ORTHOGRAPHIC { height: f32 },
+}
+
+#[derive(Clone)]
 pub struct View
 {
     pub position: Vector3,
@@ -8,6 +18,20 @@ pub struct View
     forward: Vector3,
     right: Vector3,
     up: Vector3,
+
+    projection: Projection,
+
+    // vertical field of view in degrees (perspective only)
+    fov: f32,
+    // distances to the clipping planes
+    near: f32,
+    far: f32,
+    // width over height, matched to the render target by the render whenever the target is resized
+    aspect: f32,
+    // whether the aspect ratio follows the render target, until it is set by hand
+    fitted: bool,
+    // off-axis offset of the view volume, in fractions of its width & height
+    shift: (f32, f32),
 }
 
 impl View
@@ -22,6 +46,15 @@ impl View
             forward: Vector3::create(0.0, 0.0, 1.0),
             right:   Vector3::zero(),
             up:      Vector3::create(0.0, 1.0, 0.0),
+
+            projection: Projection::PERSPECTIVE,
+
+            fov: 75.0,
+            near: 0.1,
+            far: 1000.0,
+            aspect: 1.0,
+            fitted: true,
+            shift: (0.0, 0.0),
         };
 
         view.orientate();
@@ -34,9 +67,107 @@ impl View
         Matrix4x4::view(&self.position, &(self.position + self.forward), &self.up)
     }
 
-    pub fn perspective(&self) -> Matrix4x4
+    pub fn projection(&self) -> Matrix4x4
+    {
+        // half the size of the view volume, on the near plane for a perspective
+        let half_height: f32 = match self.projection
+        {
+            Projection::PERSPECTIVE => self.near * f32::tan(f32::to_radians(self.fov) / 2.0),
+            Projection::ORTHOGRAPHIC { height } => height / 2.0,
+        };
+
+        let half_width: f32 = half_height * self.aspect;
+
+        let (x, y) = (self.shift.0 * half_width * 2.0, self.shift.1 * half_height * 2.0);
+
+        let (left, right, bottom, top) = (x - half_width, x + half_width, y - half_height, y + half_height);
+
+        match self.projection
+        {
+            Projection::PERSPECTIVE => Matrix4x4::frustum(left, right, bottom, top, self.near, self.far),
+            Projection::ORTHOGRAPHIC { .. } => Matrix4x4::orthographic(left, right, bottom, top, self.near, self.far),
+        }
+    }
+
+    pub fn set_projection(&mut self, projection: Projection)
+    {
+        if let Projection::ORTHOGRAPHIC { height } = projection
+        {
+            assert!(height > 0.0, "Error : the orthographic view volume needs a positive height");
+        }
+
+        self.projection = projection;
+    }
+
+    pub fn set_fov(&mut self, degrees: f32)
+    {
+        assert!(degrees > 0.0 && degrees < 180.0, "Error : the field of view must be between 0 and 180 degrees");
+
+        self.fov = degrees;
+    }
+
+    pub fn set_clip(&mut self, near: f32, far: f32)
+    {
+        assert!(near > 0.0 && far > near, "Error : the clipping planes need 0 < near < far");
+
+        self.near = near;
+        self.far = far;
+    }
+
+    pub fn set_aspect(&mut self, aspect: f32)
+    {
+        assert!(aspect > 0.0 && aspect.is_finite(), "Error : the aspect ratio must be positive");
+
+        self.aspect = aspect;
+        self.fitted = false;
+    }
+
+    // matches the aspect ratio to the render target, unlike set_aspect it keeps following the target
+    pub fn fit_aspect(&mut self, aspect: f32)
+    {
+        self.set_aspect(aspect);
+        self.fitted = true;
+    }
+
+    // moves the view volume sideways without turning the view, e.g. (0.5, 0.0) moves it by half its width to the right
+    pub fn set_shift(&mut self, x: f32, y: f32)
+    {
+        self.shift = (x, y);
+    }
+
+    pub fn projection_mode(&self) -> Projection
+    {
+        self.projection
+    }
+
+    pub fn fov(&self) -> f32
+    {
+        self.fov
+    }
+
+    pub fn near(&self) -> f32
+    {
+        self.near
+    }
+
+    pub fn far(&self) -> f32
+    {
+        self.far
+    }
+
+    pub fn aspect(&self) -> f32
+    {
+        self.aspect
+    }
+
+    pub fn fitted(&self) -> bool
+    {
+        self.fitted
+    }
+
+    pub fn shift(&self) -> (f32, f32)
     {
-        Matrix4x4::perspective(75.0, WIDTH as f32 / HEIGHT as f32, 0.1, 1000.0)
+        self.shift
     }
 
     pub fn translate(&mut self, x: f32, y: f32, z: f32)
@@ -44,7 +175,7 @@ impl View
         // calculate the motion vector in the view's local coordinates
         let motion: Vector3 = (self.forward * z) + (self.right * x) + (self.up * y);
 
-        self.position = self.position + motion;
+        self.position += motion;
     }
 
     pub fn rotate(&mut self, pitch: f32, yaw: f32)